@@ -0,0 +1,344 @@
+use crate::cli::flag_value;
+use crate::input::Source;
+use crate::report::Format;
+use crate::runner::Run;
+use crate::Result;
+use aoc_prelude::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const BENCH_FLAG: &str = "--bench";
+const WARMUP_FLAG: &str = "--warmup";
+const BASELINE_FLAG: &str = "--baseline";
+const SAVE_FLAG: &str = "--save-baseline";
+const THRESHOLD_FLAG: &str = "--threshold";
+
+const DEFAULT_WARMUP: usize = 3;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Column order of the baseline file and of the CSV bench output.
+pub const BENCH_CSV_HEADER: &str = "day,phase,min_ns,median_ns,mean_ns,stddev_ns";
+
+/// Settings of a `--bench N` run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    pub iterations: usize,
+    pub warmup: usize,
+    /// Baseline to compare against.
+    pub baseline: Option<PathBuf>,
+    /// Where to write the new baseline.
+    pub save: Option<PathBuf>,
+    /// Slowdown of the median, in percent, above which a phase gets flagged.
+    pub threshold: f64,
+}
+
+impl Options {
+    /// Reads `--bench N [--warmup W] [--baseline <file>] [--save-baseline <file>] [--threshold <pct>]`.
+    /// `None` unless `--bench` was passed.
+    ///
+    /// # Errors
+    ///
+    /// Fails on non-numeric or zero iteration counts and on a negative threshold.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>> {
+        let flag = |name| flag_value(args.iter().cloned(), name);
+        let Some(iterations) = flag(BENCH_FLAG) else {
+            return Ok(None);
+        };
+        let iterations = iterations.parse()?;
+        if iterations == 0 {
+            return Err("--bench needs at least one iteration".into());
+        }
+        let threshold = flag(THRESHOLD_FLAG).map_or(Ok(DEFAULT_THRESHOLD), |x| x.parse())?;
+        if threshold < 0.0 {
+            return Err(format!("negative slowdown threshold: {threshold}").into());
+        }
+        Ok(Some(Self {
+            iterations,
+            warmup: flag(WARMUP_FLAG).map_or(Ok(DEFAULT_WARMUP), |x| x.parse())?,
+            baseline: flag(BASELINE_FLAG).map(PathBuf::from),
+            save: flag(SAVE_FLAG).map(PathBuf::from),
+            threshold,
+        }))
+    }
+}
+
+/// Summary of a set of timings, all in nanoseconds.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    /// # Panics
+    ///
+    /// Panics on an empty sample.
+    #[must_use]
+    pub fn new(samples: &[u64]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarize");
+        let mut sorted = samples.iter().map(|&x| x as f64).collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        // sample standard deviation, zero for a single run
+        let stddev = if n > 1 {
+            (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        Self {
+            min: sorted[0],
+            median,
+            mean,
+            stddev,
+        }
+    }
+}
+
+/// Timings of one phase of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+impl Measurement {
+    #[must_use]
+    pub fn csv(&self) -> String {
+        let s = &self.stats;
+        format!(
+            "{},{},{:.0},{:.0},{:.0},{:.0}",
+            self.day, self.phase, s.min, s.median, s.mean, s.stddev
+        )
+    }
+
+    /// Parses a row produced by [`Measurement::csv`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the row doesn't have the [`BENCH_CSV_HEADER`] columns.
+    pub fn from_csv(row: &str) -> Result<Self> {
+        let fields = row.split(',').map(str::trim).collect::<Vec<_>>();
+        let [day, phase, min, median, mean, stddev] = fields[..] else {
+            return Err(format!("expected 6 CSV fields, got {}", fields.len()).into());
+        };
+        Ok(Self {
+            day: day.parse()?,
+            phase: phase.to_owned(),
+            stats: Stats {
+                min: min.parse()?,
+                median: median.parse()?,
+                mean: mean.parse()?,
+                stddev: stddev.parse()?,
+            },
+        })
+    }
+
+    fn json(&self) -> String {
+        let s = &self.stats;
+        format!(
+            r#"{{"day":{},"phase":"{}","min_ns":{:.0},"median_ns":{:.0},"mean_ns":{:.0},"stddev_ns":{:.0}}}"#,
+            self.day, self.phase, s.min, s.median, s.mean, s.stddev
+        )
+    }
+}
+
+/// Runs `solve` on `source` `warmup + iterations` times, timing the input read and every
+/// phase of the solver separately.
+///
+/// # Errors
+///
+/// Fails as soon as reading the input or solving fails.
+pub fn bench<F: Fn(&str) -> Run>(
+    day: u8,
+    solve: F,
+    source: &Source,
+    opts: &Options,
+) -> Result<Vec<Measurement>> {
+    if *source == Source::Stdin {
+        return Err("stdin can only be read once, benchmark an --input file instead".into());
+    }
+    let mut samples: [(&str, Vec<u64>); 4] = [
+        ("read", Vec::new()),
+        ("parse", Vec::new()),
+        ("part1", Vec::new()),
+        ("part2", Vec::new()),
+    ];
+    for i in 0..opts.warmup + opts.iterations {
+        let now = Instant::now();
+        let input = source.read()?;
+        let read = now.elapsed();
+
+        let run = solve(&input);
+        run.result?;
+
+        if i >= opts.warmup {
+            let p = run.phases;
+            for ((_, s), t) in samples.iter_mut().zip([read, p.parse, p.part1, p.part2]) {
+                s.push(t.as_nanos() as u64);
+            }
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|(phase, samples)| Measurement {
+            day,
+            phase: phase.to_owned(),
+            stats: Stats::new(&samples),
+        })
+        .collect())
+}
+
+/// Recorded timings, keyed by day and phase.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline(pub HashMap<(u8, String), Stats>);
+
+impl Baseline {
+    /// # Errors
+    ///
+    /// Fails if the file can't be read or isn't in the [`BENCH_CSV_HEADER`] format.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        data.lines()
+            .skip(1)
+            .filter(|x| !x.trim().is_empty())
+            .map(|row| Measurement::from_csv(row).map(|m| ((m.day, m.phase), m.stats)))
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Writes `measurements` as a new baseline.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be written.
+    pub fn save(path: &Path, measurements: &[Measurement]) -> Result<()> {
+        std::fs::write(path, render(measurements, Format::Csv, None))
+            .map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+
+    /// Median slowdown of `m` relative to the baseline, in percent.
+    #[must_use]
+    pub fn slowdown(&self, m: &Measurement) -> Option<f64> {
+        let old = self.0.get(&(m.day, m.phase.clone()))?;
+        (old.median > 0.0).then(|| (m.stats.median / old.median - 1.0) * 100.0)
+    }
+}
+
+/// Phases whose median got slower than the baseline by more than `threshold` percent.
+#[must_use]
+pub fn regressions<'a>(
+    measurements: &'a [Measurement],
+    baseline: &Baseline,
+    threshold: f64,
+) -> Vec<(&'a Measurement, f64)> {
+    measurements
+        .iter()
+        .filter_map(|m| Some((m, baseline.slowdown(m)?)))
+        .filter(|&(_, pct)| pct > threshold)
+        .collect()
+}
+
+/// Bench results in the requested format.
+/// The text table also shows the median change against `baseline`, flagging the ones above
+/// the threshold.
+#[must_use]
+pub fn render(
+    measurements: &[Measurement],
+    format: Format,
+    baseline: Option<(&Baseline, f64)>,
+) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Text => {
+            let _ = writeln!(
+                out,
+                "Day  Phase  {:>10}  {:>10}  {:>10}  {:>10}",
+                "min", "median", "mean", "stddev"
+            );
+            for m in measurements {
+                let s = &m.stats;
+                let _ = write!(
+                    out,
+                    " {:0>2}  {:<5}  {:>10}  {:>10}  {:>10}  {:>10}",
+                    m.day,
+                    m.phase,
+                    fmt_ns(s.min),
+                    fmt_ns(s.median),
+                    fmt_ns(s.mean),
+                    fmt_ns(s.stddev)
+                );
+                if let Some(pct) = baseline.and_then(|(b, _)| b.slowdown(m)) {
+                    let flag = baseline.is_some_and(|(_, t)| pct > t);
+                    let _ = write!(out, "  {pct:+.1}%{}", if flag { " SLOWER" } else { "" });
+                }
+                out.push('\n');
+            }
+        }
+        Format::Json => {
+            let rows = measurements
+                .iter()
+                .map(Measurement::json)
+                .collect::<Vec<_>>();
+            let _ = writeln!(out, "[{}]", rows.join(","));
+        }
+        Format::Csv => {
+            let _ = writeln!(out, "{BENCH_CSV_HEADER}");
+            for m in measurements {
+                let _ = writeln!(out, "{}", m.csv());
+            }
+        }
+    }
+    out
+}
+
+/// Prints the results, checks them against the baseline and saves the new one if asked to.
+/// Returns whether every phase stayed within the slowdown threshold.
+///
+/// # Errors
+///
+/// Fails if the baseline can't be read or written.
+pub fn report(measurements: &[Measurement], opts: &Options, format: Format) -> Result<bool> {
+    let baseline = opts.baseline.as_deref().map(Baseline::load).transpose()?;
+    let compare = baseline.as_ref().map(|b| (b, opts.threshold));
+    print!("{}", render(measurements, format, compare));
+
+    let slower = baseline.map_or_else(Vec::new, |b| regressions(measurements, &b, opts.threshold));
+    for (m, pct) in &slower {
+        eprintln!(
+            "Day {:0>2} {} is {pct:.1}% slower than the baseline (threshold {}%)",
+            m.day, m.phase, opts.threshold
+        );
+    }
+    if let Some(path) = &opts.save {
+        Baseline::save(path, measurements)?;
+    }
+    Ok(slower.is_empty())
+}
+
+/// Three significant digits in the most readable unit.
+fn fmt_ns(ns: f64) -> String {
+    let (value, unit) = match ns {
+        x if x >= 1e9 => (x / 1e9, "s"),
+        x if x >= 1e6 => (x / 1e6, "ms"),
+        x if x >= 1e3 => (x / 1e3, "μs"),
+        x => (x, "ns"),
+    };
+    let decimals = match value {
+        x if x >= 100.0 => 0,
+        x if x >= 10.0 => 1,
+        _ => 2,
+    };
+    format!("{value:.decimals$}{unit}")
+}
@@ -0,0 +1,136 @@
+use crate::report::{Record, Status};
+use crate::Result;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Recorded answers for an input: part one on the first line, part two on the second.
+/// Empty lines (or missing ones) mean that part isn't checked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub p1: Option<String>,
+    pub p2: Option<String>,
+}
+
+impl Expected {
+    #[must_use]
+    pub fn parse(s: &str) -> Self {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .map(|x| (!x.is_empty()).then(|| x.to_owned()));
+        Self {
+            p1: lines.next().flatten(),
+            p2: lines.next().flatten(),
+        }
+    }
+
+    /// Loads the answers recorded for `input_path`, `None` if there aren't any.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the answers file exists but can't be read.
+    pub fn load(input_path: &Path) -> Result<Option<Self>> {
+        match std::fs::read_to_string(answers_path(input_path)) {
+            Ok(s) => Ok(Some(Self::parse(&s))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Answers live next to their input: `inputs/07.in` is checked against `inputs/07.ans`.
+#[must_use]
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    input_path.with_extension("ans")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// Nothing recorded to compare against.
+    Unknown,
+    /// The solver itself failed.
+    Error,
+}
+
+impl Verdict {
+    fn new(expected: Option<&String>, actual: &str, status: &Status) -> Self {
+        match (status, expected) {
+            (Status::Error(_), _) => Verdict::Error,
+            (Status::Ok, None) => Verdict::Unknown,
+            (Status::Ok, Some(expected)) if expected == actual => Verdict::Pass,
+            (Status::Ok, Some(expected)) => Verdict::Fail {
+                expected: expected.clone(),
+                actual: actual.to_owned(),
+            },
+        }
+    }
+
+    fn is_ok(&self) -> bool {
+        matches!(self, Verdict::Pass | Verdict::Unknown)
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Unknown => "-",
+            Verdict::Error => "ERROR",
+        }
+    }
+}
+
+/// Verdicts for both parts of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub day: u8,
+    pub p1: Verdict,
+    pub p2: Verdict,
+}
+
+impl Check {
+    #[must_use]
+    pub fn new(record: &Record, expected: Option<&Expected>) -> Self {
+        let expected = expected.cloned().unwrap_or_default();
+        Self {
+            day: record.day,
+            p1: Verdict::new(expected.p1.as_ref(), &record.p1, &record.status),
+            p2: Verdict::new(expected.p2.as_ref(), &record.p2, &record.status),
+        }
+    }
+
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.p1.is_ok() && self.p2.is_ok()
+    }
+}
+
+/// Pass/fail matrix, followed by the details of every mismatch.
+#[must_use]
+pub fn render_matrix(checks: &[Check]) -> String {
+    let mut out = String::from("Day  Part one  Part two\n");
+    for c in checks {
+        let _ = writeln!(out, " {:0>2}  {:<8}  {}", c.day, c.p1.label(), c.p2.label());
+    }
+
+    for c in checks {
+        for (part, verdict) in [("one", &c.p1), ("two", &c.p2)] {
+            if let Verdict::Fail { expected, actual } = verdict {
+                let _ = writeln!(
+                    out,
+                    "Day {:0>2} part {part}: expected {expected}, got {actual}",
+                    c.day
+                );
+            }
+        }
+    }
+
+    let passed = checks.iter().filter(|c| c.passed()).count();
+    let _ = writeln!(out, "{passed}/{} days passed", checks.len());
+    out
+}
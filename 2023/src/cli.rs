@@ -0,0 +1,44 @@
+use crate::Result;
+
+/// Value of `--flag <value>` or `--flag=<value>`, if present.
+pub fn flag_value<I: IntoIterator<Item = String>>(args: I, flag: &str) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(flag).and_then(|x| x.strip_prefix('=')) {
+            return Some(value.to_owned());
+        }
+    }
+    None
+}
+
+/// Whether a boolean `--flag` was passed.
+pub fn has_flag<I: IntoIterator<Item = String>>(args: I, flag: &str) -> bool {
+    args.into_iter().any(|x| x == flag)
+}
+
+/// Parses a day selection such as `3,5-9` into a sorted, de-duplicated list.
+///
+/// # Errors
+///
+/// Fails on anything that isn't a comma-separated list of days or day ranges.
+pub fn parse_days(spec: &str) -> Result<Vec<u8>> {
+    let mut days = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+        match part.split_once('-') {
+            Some((lo, hi)) => {
+                let (lo, hi) = (lo.trim().parse::<u8>()?, hi.trim().parse::<u8>()?);
+                if lo > hi {
+                    return Err(format!("empty day range: {part}").into());
+                }
+                days.extend(lo..=hi);
+            }
+            None => days.push(part.parse()?),
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
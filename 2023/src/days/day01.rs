@@ -0,0 +1,46 @@
+use crate::Result;
+
+const DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn calibration(digits: &[u32]) -> Option<u32> {
+    Some(digits.first()? * 10 + digits.last()?)
+}
+
+fn digits(line: &str, spelled: bool) -> Vec<u32> {
+    let mut digits = Vec::new();
+    for (idx, c) in line.char_indices() {
+        if let Some(dig) = c.to_digit(10) {
+            digits.push(dig);
+        }
+        if spelled {
+            for (d_idx, v) in DIGITS.iter().enumerate() {
+                if line[idx..].starts_with(v) {
+                    digits.push((d_idx + 1) as u32);
+                }
+            }
+        }
+    }
+    digits
+}
+
+fn calibration_sum(lines: &[&str], spelled: bool) -> Result<u32> {
+    lines.iter().try_fold(0, |acc, line| {
+        let value =
+            calibration(&digits(line, spelled)).ok_or_else(|| format!("no digits in: {line}"))?;
+        Ok(acc + value)
+    })
+}
+
+pub fn parse(input: &str) -> Result<Vec<&str>> {
+    Ok(input.lines().collect())
+}
+
+pub fn part1(lines: &[&str]) -> Result<u32> {
+    calibration_sum(lines, false)
+}
+
+pub fn part2(lines: &[&str]) -> Result<u32> {
+    calibration_sum(lines, true)
+}
@@ -0,0 +1,101 @@
+use crate::{Grid, Result};
+use aoc_2dmap::prelude::{Map, Pos};
+use aoc_prelude::{HashSet, Itertools};
+use std::collections::VecDeque;
+
+pub enum Tile {
+    Empty,
+    Symbol(char),
+    Number(u32),
+}
+
+impl From<char> for Tile {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => Self::Empty,
+            x => {
+                if x.is_ascii_digit() {
+                    Self::Number(x.to_digit(10).unwrap())
+                } else {
+                    Self::Symbol(x)
+                }
+            }
+        }
+    }
+}
+
+fn extract_numbers<T>(map: &Map<Tile>, start: Pos) -> T
+where
+    T: FromIterator<(Pos, u64)>,
+{
+    let mut num_buf = VecDeque::new();
+    start
+        .neighbors_diag()
+        .filter(|p| matches!(map.get_ref(*p), Some(Tile::Number(_))))
+        .map(|p| expand_number(map, p, &mut num_buf))
+        .collect::<T>()
+}
+
+fn expand_number(map: &Map<Tile>, start: Pos, deq: &mut VecDeque<u32>) -> (Pos, u64) {
+    deq.clear();
+    if let Some(Tile::Number(x)) = map.get_ref(start) {
+        deq.push_front(*x);
+    }
+
+    let offset_left = Pos::new(-1, 0);
+    let offset_right = Pos::new(1, 0);
+
+    let mut left = start + offset_left;
+    let mut right = start + offset_right;
+
+    while let Some(Tile::Number(x)) = map.get_ref(left) {
+        deq.push_front(*x);
+        left += offset_left;
+    }
+
+    while let Some(Tile::Number(x)) = map.get_ref(right) {
+        deq.push_back(*x);
+        right += offset_right;
+    }
+
+    let num = deq.iter().fold(0_u64, |acc, x| acc * 10 + (*x as u64));
+
+    // the while loop left us one column left of the actual number
+    (left + offset_right, num)
+}
+
+pub fn parse(input: &str) -> Result<Map<Tile>> {
+    let grid = input.parse::<Grid>()?;
+    if grid.height() == 0 {
+        return Err("empty input".into());
+    }
+    Ok(Map::new(
+        (grid.width(), grid.height()),
+        grid.rows().flatten().map(|&c| Tile::from(c)),
+    ))
+}
+
+pub fn part1(map: &Map<Tile>) -> Result<u64> {
+    Ok(map
+        .iter()
+        .filter(|p| matches!(map.get_unchecked_ref(*p), Tile::Symbol(_)))
+        .flat_map(|p| extract_numbers::<Vec<_>>(map, p))
+        .unique()
+        .map(|(_, num)| num)
+        .sum::<u64>())
+}
+
+pub fn part2(map: &Map<Tile>) -> Result<u64> {
+    Ok(map
+        .iter()
+        .filter(|p| matches!(map.get_unchecked_ref(*p), Tile::Symbol('*')))
+        .filter_map(|p| {
+            let num_set = extract_numbers::<HashSet<_>>(map, p);
+            if num_set.len() == 2 {
+                Some(num_set.into_iter().map(|(_, num)| num).product::<u64>())
+            } else {
+                None
+            }
+        })
+        .sum::<u64>())
+}
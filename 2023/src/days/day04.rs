@@ -0,0 +1,63 @@
+use crate::Result;
+use aoc_prelude::*;
+
+#[derive(Parser)]
+#[grammar = "parsers/day04.pest"]
+pub struct CardParser;
+
+#[derive(Default, Debug, Clone)]
+struct Card {
+    draws: HashSet<u16>,
+    haves: HashSet<u16>,
+}
+
+fn process_line(line: Pair<Rule>) -> Result<Card> {
+    let mut game = Card::default();
+
+    for r in line.into_inner() {
+        match r.as_rule() {
+            Rule::Draw => {
+                game.draws.insert(r.as_str().parse()?);
+            }
+            Rule::Have => {
+                game.haves.insert(r.as_str().parse()?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(game)
+}
+
+/// Number of winning numbers on each card.
+pub fn parse(input: &str) -> Result<Vec<usize>> {
+    CardParser::parse(Rule::lines, input)?
+        .next()
+        .ok_or("no cards")?
+        .into_inner()
+        .map(|line| process_line(line).map(|c| c.haves.intersection(&c.draws).count()))
+        .collect()
+}
+
+pub fn part1(matches: &[usize]) -> Result<u32> {
+    Ok(matches
+        .iter()
+        .map(|&num| if num > 0 { 2.pow((num - 1) as u32) } else { 0 })
+        .sum())
+}
+
+pub fn part2(matches: &[usize]) -> Result<u32> {
+    let mut tally = (0..matches.len())
+        .map(|x| (x, 1))
+        .collect::<HashMap<usize, u32>>();
+
+    for (idx, &num) in matches.iter().enumerate() {
+        let offset = tally[&idx];
+
+        for j in idx + 1..=idx + num {
+            tally.entry(j).and_modify(|x| *x += offset);
+        }
+    }
+
+    Ok(tally.values().sum::<u32>())
+}
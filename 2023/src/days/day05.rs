@@ -0,0 +1,230 @@
+use crate::{Interval, Piecewise, Result};
+use aoc_prelude::*;
+
+// dest src range_len
+#[derive(Parser)]
+#[grammar = "parsers/day05.pest"]
+pub struct LookupParser;
+
+#[derive(Debug)]
+pub struct FnMap<'a> {
+    from: &'a str,
+    to: &'a str,
+    fns: Piecewise<u64>,
+}
+
+impl FnMap<'_> {
+    fn lookup(&self, x: u64) -> u64 {
+        self.fns.apply(x)
+    }
+
+    /// Where all of `intervals` end up, each number through the same function [`Self::lookup`]
+    /// would take it, as few disjoint intervals as there can be.
+    fn lookup_interval(&self, intervals: &[Interval<u64>]) -> Vec<Interval<u64>> {
+        Interval::normalize(intervals.iter().flat_map(|&i| self.fns.image(i)))
+    }
+}
+
+fn extract_number(pair: Option<Pair<Rule>>) -> Result<u64> {
+    Ok(pair.ok_or("no number")?.as_str().parse::<u64>()?)
+}
+
+fn extract_lookup(pair: Pair<Rule>) -> Result<FnMap> {
+    let inner = pair.into_inner();
+    let mut def = inner.peek().ok_or("no def")?.into_inner();
+    let from = def.next().ok_or("no source")?.as_str();
+    let to = def.next().ok_or("no destination")?.as_str();
+
+    let fns = inner
+        .filter(|x| x.as_rule() == Rule::Lookup)
+        .map(|x| {
+            let line = x.as_str();
+            let mut inner = x.into_inner();
+            let dest = extract_number(inner.next())?;
+            let src = extract_number(inner.next())?;
+            let sz = extract_number(inner.next())?;
+            let overflow = || format!("range past u64::MAX: {line}");
+            dest.checked_add(sz).ok_or_else(overflow)?;
+            let end = src.checked_add(sz).ok_or_else(overflow)?;
+            Ok((Interval::new(src, end), dest))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(FnMap { from, to, fns })
+}
+
+/// Seeds and the maps between categories, keyed by their source category.
+pub struct Almanac<'a> {
+    seeds: Vec<u64>,
+    chain: HashMap<&'a str, FnMap<'a>>,
+}
+
+fn next_map<'a>(ptr: &str, chain: &'a HashMap<&str, FnMap<'a>>) -> Result<&'a FnMap<'a>> {
+    Ok(chain
+        .get(ptr)
+        .ok_or_else(|| format!("no map from: {ptr}"))?)
+}
+
+fn seed_to_location(seed: u64, chain: &HashMap<&str, FnMap>) -> Result<u64> {
+    let mut ptr = "seed";
+    let mut look_for = seed;
+    while ptr != "location" {
+        let lmap = next_map(ptr, chain)?;
+        look_for = lmap.lookup(look_for);
+        ptr = lmap.to;
+    }
+    Ok(look_for)
+}
+
+fn seed_range_to_loc_range(
+    range: Interval<u64>,
+    chain: &HashMap<&str, FnMap>,
+) -> Result<Vec<Interval<u64>>> {
+    let mut ptr = "seed";
+    let mut intervals = vec![range];
+    while ptr != "location" {
+        let lmap = next_map(ptr, chain)?;
+        intervals = lmap.lookup_interval(&intervals);
+        ptr = lmap.to;
+    }
+    Ok(intervals)
+}
+
+pub fn parse(input: &str) -> Result<Almanac<'_>> {
+    let parsed = LookupParser::parse(Rule::root, input)?
+        .next()
+        .ok_or("no root")?
+        .into_inner();
+
+    let seeds = parsed
+        .peek()
+        .ok_or("no seeds")?
+        .into_inner()
+        .map(|x| x.as_str().parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let chain = parsed
+        .filter(|x| x.as_rule() == Rule::lookup_table)
+        .map(|x| extract_lookup(x).map(|l| (l.from, l)))
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    Ok(Almanac { seeds, chain })
+}
+
+pub fn part1(almanac: &Almanac) -> Result<u64> {
+    Ok(almanac
+        .seeds
+        .iter()
+        .map(|&s| seed_to_location(s, &almanac.chain))
+        .process_results(|locs| locs.min())?
+        .ok_or("at least one seed")?)
+}
+
+pub fn part2(almanac: &Almanac) -> Result<u64> {
+    // part one takes any seeds, only here do they come in start and length pairs
+    if !almanac.seeds.len().is_multiple_of(2) {
+        return Err(format!("{} seeds don't pair up into ranges", almanac.seeds.len()).into());
+    }
+    Ok(almanac
+        .seeds
+        .chunks_exact(2)
+        .map(|x| {
+            let end = x[0].checked_add(x[1]).ok_or("seed range past u64::MAX")?;
+            seed_range_to_loc_range(Interval::new(x[0], end), &almanac.chain)
+        })
+        .process_results(|ranges| ranges.flatten().map(|i| i.start).min())?
+        .ok_or("no answer")?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn fn_maps() -> impl Strategy<Value = FnMap<'static>> {
+        prop::collection::vec((0..100u64, 0..100u64, 0..30u64), 0..6).prop_map(|fns| FnMap {
+            from: "seed",
+            to: "location",
+            fns: fns
+                .into_iter()
+                .map(|(dest, src, sz)| (Interval::new(src, src + sz), dest))
+                .collect(),
+        })
+    }
+
+    fn seed_ranges() -> impl Strategy<Value = Interval<u64>> {
+        (0..150u64, 0..50u64).prop_map(|(start, len)| Interval::new(start, start + len))
+    }
+
+    #[test]
+    fn rejects_overflowing_ranges() {
+        let max = u64::MAX;
+        assert!(parse(&format!("seeds: 1 2\n\nseed-to-location map:\n0 {max} 2")).is_err());
+        assert!(parse(&format!("seeds: 1 2\n\nseed-to-location map:\n{max} 0 2")).is_err());
+        let input = format!("seeds: {max} 2\n\nseed-to-location map:\n0 0 1");
+        assert!(part2(&parse(&input).unwrap()).is_err());
+    }
+
+    #[test]
+    fn unpaired_seed() {
+        let almanac = parse(
+            "seeds: 79 14 55
+
+seed-to-location map:
+0 0 1",
+        )
+        .unwrap();
+        assert_eq!(part1(&almanac).unwrap(), 14);
+        assert_eq!(
+            part2(&almanac).unwrap_err().to_string(),
+            "3 seeds don't pair up into ranges"
+        );
+    }
+
+    #[test]
+    fn each_piece_once() {
+        let map = FnMap {
+            from: "seed",
+            to: "location",
+            fns: [(Interval::new(10, 20), 100), (Interval::new(30, 40), 200)]
+                .into_iter()
+                .collect(),
+        };
+        // misses the first function but not the second, and the unmapped gaps come out as they are
+        assert_eq!(
+            map.lookup_interval(&[Interval::new(25, 45)]),
+            [
+                Interval::new(25, 30),
+                Interval::new(40, 45),
+                Interval::new(200, 210)
+            ]
+        );
+        assert_eq!(
+            map.lookup_interval(&[Interval::new(0, 5)]),
+            [Interval::new(0, 5)]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn intervals_match_lookup(map in fn_maps(), seeds in seed_ranges()) {
+            let mut expected = (seeds.start..seeds.end).map(|x| map.lookup(x)).collect::<Vec<_>>();
+            expected.sort_unstable();
+            expected.dedup();
+
+            let mapped = map.lookup_interval(&[seeds]);
+            let points = mapped.iter().flat_map(|i| i.start..i.end).collect::<Vec<_>>();
+            prop_assert_eq!(points, expected);
+        }
+
+        #[test]
+        fn chain_matches_lookup(seeds in seed_ranges()) {
+            let almanac = parse(include_str!("../../tests/examples/05.ex")).unwrap();
+            let lowest = (seeds.start..seeds.end)
+                .map(|s| seed_to_location(s, &almanac.chain).unwrap())
+                .min();
+            let ranges = seed_range_to_loc_range(seeds, &almanac.chain).unwrap();
+            prop_assert_eq!(ranges.first().map(|i| i.start), lowest);
+        }
+    }
+}
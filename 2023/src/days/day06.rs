@@ -0,0 +1,108 @@
+use crate::Result;
+
+fn extract_numbers(s: &str) -> Vec<u64> {
+    s.split_whitespace()
+        .filter_map(|w| w.parse::<u64>().ok())
+        .collect()
+}
+
+/// Ways to beat `dmin` in a race of `tt`: holding the button for `x` goes `x * (tt - x)`.
+/// None at all if the record is out of reach.
+///
+/// The winning holds are the integers strictly between the roots of `x² - tt·x + dmin`, which
+/// lie symmetrically around `tt / 2`. The integer square root of the discriminant gets within
+/// one of the lower root, and checking the distances either side pins down the first win.
+fn count_solutions(tt: u64, dmin: u64) -> Result<u64> {
+    let (t, d) = (u128::from(tt), u128::from(dmin));
+    let wins = |x: u128| x * (t - x) > d;
+
+    let Some(disc) = (t * t).checked_sub(4 * d) else {
+        return Ok(0);
+    };
+    let mut first = (t - disc.isqrt()) / 2;
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
+    while first <= t / 2 && !wins(first) {
+        first += 1;
+    }
+    if first > t / 2 {
+        return Ok(0);
+    }
+    Ok(u64::try_from(t + 1 - 2 * first)?)
+}
+
+fn concat(v: &[u64]) -> Result<u64> {
+    Ok(v.iter()
+        .fold(String::new(), |acc, x| format!("{acc}{x}"))
+        .parse::<u64>()?)
+}
+
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+}
+
+pub fn parse(input: &str) -> Result<Races> {
+    let input = input.lines().map(extract_numbers).collect::<Vec<_>>();
+    let [times, distances] =
+        <[_; 2]>::try_from(input).map_err(|_| "expected a time line and a distance line")?;
+    Ok(Races { times, distances })
+}
+
+pub fn part1(races: &Races) -> Result<u64> {
+    let ways = races
+        .times
+        .iter()
+        .zip(races.distances.iter())
+        .map(|(tt, dmin)| count_solutions(*tt, *dmin))
+        .product::<Result<u64>>()?;
+    Ok(ways)
+}
+
+pub fn part2(races: &Races) -> Result<u64> {
+    count_solutions(concat(&races.times)?, concat(&races.distances)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(tt: u64, dmin: u64) -> u64 {
+        (0..=tt).filter(|x| x * (tt - x) > dmin).count() as u64
+    }
+
+    #[test]
+    fn example() {
+        let races = parse(include_str!("../../tests/examples/06.ex")).unwrap();
+        assert_eq!(part1(&races).unwrap(), 288);
+        assert_eq!(part2(&races).unwrap(), 71503);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for tt in 0..60 {
+            for dmin in 0..=tt * tt / 4 + 1 {
+                assert_eq!(
+                    count_solutions(tt, dmin).unwrap(),
+                    brute_force(tt, dmin),
+                    "{tt} {dmin}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_races() {
+        // only holding for half the race goes 2^64, one further than the record
+        let half = 1 << 32;
+        assert_eq!(count_solutions(2 * half, u64::MAX).unwrap(), 1);
+        assert_eq!(count_solutions(2 * half - 1, u64::MAX).unwrap(), 0);
+        // a discriminant of 36 next to a t² of 2^64, which f64 rounds away
+        let t = 1 << 32;
+        let first = (1u64 << 31) - 3;
+        let dmin = first * (t - first);
+        assert_eq!(count_solutions(t, dmin).unwrap(), 5);
+        assert_eq!(count_solutions(u64::MAX, 0).unwrap(), u64::MAX - 1);
+    }
+}
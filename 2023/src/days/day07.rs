@@ -0,0 +1,104 @@
+use crate::Result;
+use aoc_prelude::{HashMap, Itertools};
+
+// tuple within tuple => can compare
+type Score = (u16, Option<(char, char, char, char, char)>);
+
+#[derive(Debug, Clone)]
+pub struct Hand<'a> {
+    cards: &'a str,
+    value: u16,
+}
+
+fn hand_score(cards: &str, p2: bool, counter: &mut HashMap<char, u16>) -> Result<Score> {
+    counter.clear();
+    let mut most_numerous = 'J';
+    let mut max_tally = 0;
+
+    for c in cards.chars() {
+        counter.entry(c).and_modify(|x| *x += 1).or_insert(1);
+        if p2 && c != 'J' && counter[&c] > max_tally {
+            max_tally = counter[&c];
+            most_numerous = c;
+        }
+    }
+
+    // replace the most numerous card with jokers for part 2
+    if p2 && most_numerous != 'J' && counter.contains_key(&'J') {
+        let num_jokers = counter[&'J'];
+        counter
+            .entry(most_numerous)
+            .and_modify(|x| *x += num_jokers);
+        counter.remove(&'J');
+    }
+
+    let rank = match counter.values().sorted().as_slice() {
+        [5] => 100,
+        [1, 4] => 90,
+        [2, 3] => 80,
+        [1, 1, 3] => 70,
+        [1, 2, 2] => 60,
+        [1, 1, 1, 2] => 50,
+        [1, 1, 1, 1, 1] => 40,
+        _ => return Err(format!("invalid hand: {cards}").into()),
+    };
+
+    // downgrade joker value to '< 2' for part 2
+    let j_replace = if p2 { '1' } else { 'w' };
+
+    Ok((
+        rank,
+        cards
+            .chars()
+            .map(|c| match c {
+                'A' => 'z',
+                'K' => 'y',
+                'Q' => 'x',
+                'J' => j_replace,
+                'T' => 'v',
+                other => other,
+            })
+            .collect_tuple::<(_, _, _, _, _)>(),
+    ))
+}
+
+fn total_score(hands: &[Hand], p2: bool) -> Result<usize> {
+    // re-use counter allocation
+    let mut counter = HashMap::new();
+
+    let mut bids = hands
+        .iter()
+        .map(|h| Ok((hand_score(h.cards, p2, &mut counter)?, h.value)))
+        .collect::<Result<Vec<_>>>()?;
+    bids.sort_unstable();
+
+    Ok(bids
+        .iter()
+        .enumerate()
+        .map(|(i, (_, value))| (i + 1) * (*value as usize))
+        .sum::<usize>())
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hand<'_>>> {
+    input
+        .lines()
+        .map(|line| {
+            let (cards, value) = line
+                .split_whitespace()
+                .collect_tuple()
+                .ok_or_else(|| format!("invalid line: {line}"))?;
+            Ok(Hand {
+                cards,
+                value: value.parse()?,
+            })
+        })
+        .collect()
+}
+
+pub fn part1(hands: &[Hand]) -> Result<usize> {
+    total_score(hands, false)
+}
+
+pub fn part2(hands: &[Hand]) -> Result<usize> {
+    total_score(hands, true)
+}
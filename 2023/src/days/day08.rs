@@ -0,0 +1,151 @@
+use crate::{export, Result};
+use aoc_prelude::*;
+use rayon::prelude::*;
+
+#[derive(Parser)]
+#[grammar = "parsers/day08.pest"]
+pub struct NodeParser;
+
+type Node<'a> = &'a str;
+
+type Graph<'a> = HashMap<Node<'a>, (Node<'a>, Node<'a>)>;
+
+fn extract_node<'a>(pairs: &mut Pairs<'a, Rule>) -> Result<Node<'a>> {
+    Ok(pairs.next().ok_or("expected a node")?.as_str())
+}
+
+fn steps_until<P: Fn(Node) -> bool>(
+    graph: &Graph,
+    instr: &mut impl Iterator<Item = char>,
+    start: Node,
+    accept: P,
+) -> Result<BigInt> {
+    let mut ans = BigInt::from(0);
+    let mut cur = start;
+    for i in instr {
+        if accept(cur) {
+            return Ok(ans);
+        }
+        ans += 1;
+        let &(left, right) = graph
+            .get(cur)
+            .ok_or_else(|| format!("unknown node: {cur}"))?;
+        match i {
+            'L' => cur = left,
+            'R' => cur = right,
+            _ => return Err(format!("invalid instruction: {i}").into()),
+        }
+    }
+    if accept(cur) {
+        Ok(ans)
+    } else {
+        Err(format!("no end in sight after {ans} steps from {start}").into())
+    }
+}
+
+pub struct Network<'a> {
+    instructions: &'a str,
+    graph: Graph<'a>,
+}
+
+impl Network<'_> {
+    /// Gives up once every node has been left by every instruction, as it's going in circles.
+    fn steps_until<P: Fn(Node) -> bool>(&self, start: Node, accept: P) -> Result<BigInt> {
+        let limit = self.graph.len() * self.instructions.len();
+        steps_until(
+            &self.graph,
+            &mut self.instructions.chars().cycle().take(limit),
+            start,
+            accept,
+        )
+    }
+}
+
+pub fn parse(input: &str) -> Result<Network<'_>> {
+    let mut input = input.lines();
+    let instructions = input.next().ok_or("no lines")?;
+
+    let graph = input
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let pairs = &mut NodeParser::parse(Rule::line, line)?;
+            Ok((
+                extract_node(pairs)?,
+                (extract_node(pairs)?, extract_node(pairs)?),
+            ))
+        })
+        .collect::<Result<Graph>>()?;
+
+    Ok(Network {
+        instructions,
+        graph,
+    })
+}
+
+pub fn part1(network: &Network) -> Result<BigInt> {
+    network.steps_until("AAA", |cur| cur == "ZZZ")
+}
+
+pub fn part2(network: &Network) -> Result<BigInt> {
+    network
+        .graph
+        .keys()
+        .filter(|x| x.ends_with('A'))
+        .par_bridge()
+        .map(|start| network.steps_until(start, |cur| cur.ends_with('Z')))
+        // there's only one matching target node for each starting node, so lcm is alright!
+        .try_reduce(|| BigInt::from(1), |a, b| Ok(num_integer::lcm(a, b)))
+}
+
+/// The network, with the way from AAA to ZZZ highlighted if there is one.
+pub fn graph(network: &Network) -> Result<export::Graph> {
+    let mut graph = export::Graph::directed();
+    for (node, (left, right)) in network.graph.iter().sorted() {
+        graph
+            .node(node, node)
+            .edge(node, left, Some("L".to_owned()))
+            .edge(node, right, Some("R".to_owned()));
+    }
+
+    if network.graph.contains_key("AAA") {
+        // a cycle through every node and instruction without ZZZ never gets there
+        let limit = network.graph.len() * network.instructions.len();
+        let mut cur = "AAA";
+        for i in network.instructions.chars().cycle().take(limit) {
+            if cur == "ZZZ" {
+                break;
+            }
+            let &(left, right) = network
+                .graph
+                .get(cur)
+                .ok_or_else(|| format!("unknown node: {cur}"))?;
+            let next = if i == 'L' { left } else { right };
+            graph.highlight_edge(cur, next);
+            cur = next;
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_lines() {
+        assert!(parse("LR\n\nAAA = (BBB, ZZZ)\nBBB = (ZZZ)").is_err());
+        assert!(parse("LR\n\nAAA = (BBB, ZZZ) and more").is_err());
+        assert!(parse("LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").is_ok());
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let network = parse("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(
+            part1(&network).unwrap_err().to_string(),
+            "no end in sight after 6 steps from AAA"
+        );
+        let network = parse("LLR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        assert_eq!(part1(&network).unwrap(), BigInt::from(3));
+    }
+}
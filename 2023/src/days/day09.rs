@@ -0,0 +1,71 @@
+use crate::Result;
+use std::mem;
+
+fn extract_nums(s: &str) -> Vec<i32> {
+    s.split_whitespace()
+        .filter_map(|w| w.parse().ok())
+        .collect()
+}
+
+fn outer(xs: &[i32], buf: &mut Vec<i32>, inner_buf: &mut Vec<i32>) -> Result<(i32, i32)> {
+    *buf = xs.to_vec();
+
+    let mut post = 0;
+    let mut pre = 0;
+    let mut signum = 1;
+
+    if buf.len() < 2 {
+        return Err(format!("need at least two values, got: {xs:?}").into());
+    }
+
+    loop {
+        let last = buf[buf.len() - 1];
+        post += last;
+
+        pre += signum * buf[0];
+        signum = -signum;
+
+        if last == 0 && buf[buf.len() - 2] == 0 {
+            break;
+        }
+        inner(buf, inner_buf);
+        mem::swap(buf, inner_buf);
+    }
+
+    Ok((post, pre))
+}
+
+fn inner(xs: &[i32], buf: &mut Vec<i32>) {
+    *buf = xs
+        .iter()
+        .skip(1)
+        .zip(xs.iter())
+        .map(|(t1, t2)| *t1 - *t2)
+        .collect();
+}
+
+fn extrapolate(histories: &[Vec<i32>]) -> Result<Vec<(i32, i32)>> {
+    let mut outer_buf = Vec::new();
+    let mut inner_buf = Vec::new();
+
+    histories
+        .iter()
+        .map(|xs| outer(xs, &mut outer_buf, &mut inner_buf))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>> {
+    Ok(input
+        .lines()
+        .map(extract_nums)
+        .filter(|nums| !nums.is_empty())
+        .collect())
+}
+
+pub fn part1(histories: &[Vec<i32>]) -> Result<i32> {
+    Ok(extrapolate(histories)?.iter().map(|x| x.0).sum())
+}
+
+pub fn part2(histories: &[Vec<i32>]) -> Result<i32> {
+    Ok(extrapolate(histories)?.iter().map(|x| x.1).sum())
+}
@@ -0,0 +1,142 @@
+use crate::render::{Frame, Rgb};
+use crate::{Grid, Result};
+use aoc_2dmap::prelude::{Map, Pos};
+use aoc_prelude::{lazy_static, ArrayVec, HashMap, HashSet};
+
+type Dir = usize;
+
+static NORTH: Dir = 0;
+static EAST: Dir = 1;
+static SOUTH: Dir = 2;
+static WEST: Dir = 3;
+static OFFSET: [(i8, i8); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+lazy_static! {
+    static ref NEIGHS: HashMap<char, ArrayVec<Pos, 2>> = {
+        let mut res = HashMap::new();
+        for (c, dir) in [
+            ('F', [SOUTH, EAST]),
+            ('L', [NORTH, EAST]),
+            ('7', [SOUTH, WEST]),
+            ('J', [NORTH, WEST]),
+            ('|', [NORTH, SOUTH]),
+            ('-', [WEST, EAST]),
+        ] {
+            res.insert(c, dir.map(|c| Pos::from(OFFSET[c])).into());
+        }
+        res
+    };
+}
+
+// Shoelace theorem, there is no escaping
+fn shoelace(vertices: &[Pos]) -> i32 {
+    assert!(vertices.len() >= 3);
+
+    let a0 = vertices[0].y * (vertices[vertices.len() - 1].x - vertices[1].x);
+
+    (a0 + (1..vertices.len() - 1)
+        .map(|i| vertices[i].y * (vertices[i - 1].x - vertices[i + 1].x))
+        .sum::<i32>())
+        / 2
+}
+
+fn pipe_map(input: &str) -> Result<Map<char>> {
+    let grid = input.parse::<Grid>()?;
+    if grid.height() == 0 {
+        return Err("empty input".into());
+    }
+    Ok(grid.into())
+}
+
+/// The pipe loop, starting and ending at the start tile.
+fn trace_loop(map: &Map<char>) -> Result<Vec<Pos>> {
+    let start = map
+        .iter()
+        .find(|x| map.get_unchecked(*x) == 'S')
+        .ok_or("no start")?;
+
+    let can_go = start
+        .neighbors_simple()
+        .filter(|&pos| {
+            map.get_ref(pos)
+                .and_then(|c| NEIGHS.get(c))
+                .is_some_and(|av| av[0] + pos == start || av[1] + pos == start)
+        })
+        .collect::<Vec<_>>();
+
+    if can_go.len() != 2 {
+        return Err(format!("start pos: {start:?} not on the loop").into());
+    }
+
+    let mut cur = can_go[0];
+    let mut loop_nodes = HashSet::<Pos>::from([start, cur]);
+    let mut loop_nodes_v = vec![start, cur];
+
+    while cur != can_go[1] {
+        let next = NEIGHS[map.get_unchecked_ref(cur)]
+            .iter()
+            .map(|n| cur + *n)
+            .find(|p| !loop_nodes.contains(p))
+            .ok_or("we're on the loop but can't go anywhere...")?;
+        loop_nodes.insert(next);
+        loop_nodes_v.push(next);
+        cur = next;
+    }
+    loop_nodes_v.push(start);
+
+    Ok(loop_nodes_v)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Pos>> {
+    trace_loop(&pipe_map(input)?)
+}
+
+pub fn part1(loop_nodes: &[Pos]) -> Result<usize> {
+    // the start tile is in there twice
+    Ok((loop_nodes.len() - 1).div_ceil(2))
+}
+
+pub fn part2(loop_nodes: &[Pos]) -> Result<i32> {
+    Ok(shoelace(loop_nodes).abs() - loop_nodes.len() as i32 / 2 + 1)
+}
+
+/// The loop in green, the start in red and the tiles it encloses in yellow.
+pub fn render(input: &str) -> Result<Vec<Frame>> {
+    let map = pipe_map(input)?;
+    let loop_nodes = trace_loop(&map)?;
+    let on_loop = loop_nodes.iter().copied().collect::<HashSet<_>>();
+
+    let start = loop_nodes[0];
+    let north = Pos::from(OFFSET[NORTH]);
+    let goes_north = |p: Pos| {
+        if p == start {
+            // the start connects to the first and last step of the loop
+            [loop_nodes[1], loop_nodes[loop_nodes.len() - 2]].contains(&(start + north))
+        } else {
+            map.get_ref(p)
+                .and_then(|c| NEIGHS.get(c))
+                .is_some_and(|n| n.contains(&north))
+        }
+    };
+
+    // a tile is inside if a ray to its left crosses the loop an odd number of times
+    let mut inside = Vec::new();
+    for y in 0..map.size.y {
+        let mut crossings = 0;
+        for x in 0..map.size.x {
+            let p = Pos::new(x, y);
+            if on_loop.contains(&p) {
+                crossings += usize::from(goes_north(p));
+            } else if crossings % 2 == 1 {
+                inside.push(p);
+            }
+        }
+    }
+
+    let mut frame = Frame::new(&map);
+    frame
+        .highlight_all(loop_nodes.iter().copied(), Rgb::GREEN)
+        .highlight_all(inside, Rgb::YELLOW)
+        .highlight(start, Rgb::RED);
+    Ok(vec![frame])
+}
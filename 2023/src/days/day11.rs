@@ -0,0 +1,75 @@
+use crate::{Grid, Result, Symmetric};
+use aoc_prelude::PrimInt;
+use std::ops::AddAssign;
+
+fn psa_elements<'a, P: PrimInt + 'a>(c_map: &'a Grid, scale: P) -> impl Iterator<Item = P> + 'a {
+    c_map.rows().map(move |content| {
+        if content.iter().all(|c| c == &'.') {
+            scale
+        } else {
+            P::from(1).unwrap()
+        }
+    })
+}
+
+fn make_psa<P: PrimInt>(xs: impl Iterator<Item = P>) -> Vec<P> {
+    let mut res = Vec::new();
+    let mut l_sum = P::from(0).unwrap();
+    for x in xs {
+        let n_sum = l_sum + x;
+        res.push(n_sum);
+        l_sum = n_sum;
+    }
+    res
+}
+
+fn total_distance<P: PrimInt + AddAssign>(c_map: &Grid, scale: P) -> P {
+    let psa = make_psa(psa_elements(c_map, scale));
+
+    let mut total = P::from(0).unwrap();
+    let mut num_seen = P::from(0).unwrap();
+    let mut cumulative = P::from(0).unwrap();
+    c_map
+        .rows()
+        .map(|x| x.iter().filter(|&c| c == &'#').count())
+        .enumerate()
+        .for_each(|(index, count)| {
+            let count_p = P::from(count).unwrap();
+            total += (num_seen * psa[index] - cumulative) * count_p;
+            cumulative += psa[index] * count_p;
+            num_seen += count_p;
+        });
+    total
+}
+
+/// Sum of the distances between all galaxies, with empty rows and columns `scale` times wider.
+fn expanded_distance(c_map: &Grid, scale: u64) -> u64 {
+    let mut transposed = c_map.clone();
+    transposed.transpose();
+
+    total_distance(c_map, scale) + total_distance(&transposed, scale)
+}
+
+pub fn parse(input: &str) -> Result<Grid> {
+    Ok(input.parse()?)
+}
+
+pub fn part1(c_map: &Grid) -> Result<u64> {
+    Ok(expanded_distance(c_map, 2))
+}
+
+pub fn part2(c_map: &Grid) -> Result<u64> {
+    Ok(expanded_distance(c_map, 1_000_000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_expansion() {
+        let c_map = parse(include_str!("../../tests/examples/11.ex")).unwrap();
+        assert_eq!(expanded_distance(&c_map, 10), 1030);
+        assert_eq!(expanded_distance(&c_map, 100), 8410);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::Result;
+use aoc_prelude::{HashMap, Itertools};
+use std::iter;
+
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+struct State {
+    char_idx: usize,
+    run_idx: usize,
+    current_run: usize,
+}
+
+impl State {
+    fn new(char_idx: usize, run_idx: usize, current_run: usize) -> Self {
+        Self {
+            char_idx,
+            run_idx,
+            current_run,
+        }
+    }
+
+    fn advance_char(&self) -> Self {
+        Self::new(self.char_idx + 1, self.run_idx, self.current_run)
+    }
+
+    fn start_next_run(&self) -> Self {
+        Self::new(self.char_idx, self.run_idx + 1, 0)
+    }
+
+    fn increase_run(&self) -> Self {
+        Self::new(self.char_idx, self.run_idx, self.current_run + 1)
+    }
+}
+
+struct World<'a> {
+    cfg: &'a str,
+    runs: &'a Vec<usize>,
+    cache: &'a mut HashMap<State, usize>,
+}
+
+fn find_combos(world: &mut World, state: State) -> usize {
+    if let Some(&cached) = world.cache.get(&state) {
+        return cached;
+    }
+
+    let (cfg, runs) = (world.cfg, world.runs);
+    let State {
+        char_idx,
+        run_idx,
+        current_run,
+    } = state;
+
+    if char_idx == cfg.len() {
+        return usize::from(
+            (run_idx == runs.len() && current_run == 0)
+                || (run_idx == runs.len() - 1 && runs[run_idx] == current_run),
+        );
+    }
+
+    let mut ans = 0;
+    let cur_char = &cfg[char_idx..=char_idx];
+    let is_wildcard = cur_char == "?";
+
+    if is_wildcard || cur_char == "." {
+        if current_run == 0 {
+            // we placed a '.' and we're not currently in a run => advance the char idx
+            ans += find_combos(world, state.advance_char());
+        } else if run_idx < runs.len() && runs[run_idx] == current_run {
+            // we placed a '.' during a matching run, successfully completing it
+            // => advance the char idx & start a new run
+            ans += find_combos(world, state.advance_char().start_next_run());
+        }
+    }
+
+    if is_wildcard || cur_char == "#" {
+        // we placed a '#' => increase the current run
+        ans += find_combos(world, state.advance_char().increase_run());
+    }
+
+    world.cache.insert(state, ans);
+    ans
+}
+
+/// A row of springs and the lengths of its damaged runs.
+pub struct Record<'a> {
+    cfg: &'a str,
+    runs: Vec<usize>,
+}
+
+fn arrangements(records: &[Record], folds: usize) -> usize {
+    let mut cache = HashMap::with_capacity(4096);
+    let mut runs = Vec::with_capacity(4096);
+
+    records
+        .iter()
+        .map(|record| {
+            let cfg = iter::repeat_n(record.cfg, folds).join("?");
+
+            runs.clear();
+            runs.extend(iter::repeat_n(&record.runs, folds).flatten());
+
+            cache.clear();
+            let mut world = World {
+                cfg: &cfg,
+                runs: &runs,
+                cache: &mut cache,
+            };
+            find_combos(&mut world, State::new(0, 0, 0))
+        })
+        .sum()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Record<'_>>> {
+    input
+        .lines()
+        .map(|line| {
+            let mut words = line.split_whitespace();
+
+            let cfg = words.next().ok_or("no springs")?;
+            let runs = words
+                .next()
+                .ok_or_else(|| format!("no runs in: {line}"))?
+                .split(',')
+                .filter_map(|x| x.parse::<usize>().ok())
+                .collect();
+
+            Ok(Record { cfg, runs })
+        })
+        .collect()
+}
+
+pub fn part1(records: &[Record]) -> Result<usize> {
+    Ok(arrangements(records, 1))
+}
+
+pub fn part2(records: &[Record]) -> Result<usize> {
+    Ok(arrangements(records, 5))
+}
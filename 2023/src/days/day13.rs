@@ -0,0 +1,90 @@
+use crate::{Grid, Result, Symmetric};
+use aoc_prelude::Itertools;
+
+/// Mirrors between rows, worth `factor` per row above them. Only those reaching an edge count,
+/// best first; without any, a placeholder that doesn't.
+fn find_reflection(map: &Grid, factor: usize) -> Vec<(usize, bool)> {
+    let rows = map.rows().collect::<Vec<_>>();
+    let dim = rows.len();
+
+    // find matching consecutive rows and walk out from each cand and count matching rows
+    let mut res = Vec::new();
+    for cand in (0..dim.saturating_sub(1)).filter(|&r| rows[r] == rows[r + 1]) {
+        let next_up = (0..=cand)
+            .rev()
+            .zip(cand + 1..dim)
+            .take_while(|&(ra, rb)| rows[ra] == rows[rb])
+            .count();
+
+        let to_upper_edge = cand + 1;
+        let to_lower_edge = dim - to_upper_edge;
+
+        let touches_edge = (next_up == to_upper_edge) || (next_up == to_lower_edge);
+
+        if touches_edge {
+            res.push((to_upper_edge * factor, true));
+        }
+    }
+
+    if res.is_empty() {
+        vec![(dim * factor, false)]
+    } else {
+        res.sort_by(|x, y| y.cmp(x));
+        res
+    }
+}
+
+/// Mirrors between rows, then between columns: the rows of the transposed map.
+fn reflections(m: &Grid) -> impl Iterator<Item = (usize, bool)> {
+    let mut transposed = m.clone();
+    transposed.transpose();
+    find_reflection(m, 100)
+        .into_iter()
+        .chain(find_reflection(&transposed, 1))
+}
+
+fn variations(m: &Grid) -> impl Iterator<Item = Grid> + '_ {
+    m.positions().map(|p| {
+        let mut new_m = m.clone();
+        if let Some(c) = new_m.get_mut(p) {
+            *c = match *c {
+                '#' => '.',
+                _ => '#',
+            };
+        }
+        new_m
+    })
+}
+
+fn summarize(m: &Grid) -> Result<(usize, bool)> {
+    Ok(reflections(m).find(|x| x.1).ok_or("no reflection!")?)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Grid>> {
+    let mut maps = Vec::new();
+    for (is_empty, group) in &input.lines().chunk_by(|l| l.is_empty()) {
+        if !is_empty {
+            maps.push(group.collect::<Vec<_>>().join("\n").parse()?);
+        }
+    }
+    Ok(maps)
+}
+
+pub fn part1(maps: &[Grid]) -> Result<usize> {
+    maps.iter().map(|m| Ok(summarize(m)?.0)).sum()
+}
+
+pub fn part2(maps: &[Grid]) -> Result<usize> {
+    maps.iter()
+        .map(|m| {
+            let p1_ref = summarize(m)?;
+
+            let mut p2_o = None;
+            for mv in variations(m) {
+                p2_o = p2_o.or(reflections(&mv).find(|&x| x.1 && x != p1_ref));
+            }
+
+            Ok(p2_o.map(|x| x.0).ok_or("no new reflection!")?)
+        })
+        .sum()
+}
@@ -0,0 +1,286 @@
+use crate::render::{Frame, Rgb};
+use crate::{Grid, Result};
+use aoc_cycles::Cycle;
+
+/// Widest platform that fits a row in a bitset.
+const MAX_WIDTH: usize = u128::BITS as usize;
+
+/// Spin cycles shown by `--render`.
+const RENDERED_CYCLES: usize = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Tilt {
+    North,
+    West,
+    South,
+    East,
+}
+
+const SPIN: [Tilt; 4] = [Tilt::North, Tilt::West, Tilt::South, Tilt::East];
+
+/// The rounded rocks, one bitset per row: bit `x` of row `y` is set if there's a rock at `(x, y)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rocks(Vec<u128>);
+
+/// Where the cube-shaped rocks are, which never move, and how the rounded ones start out.
+pub struct Platform {
+    width: usize,
+    cubes: Vec<u128>,
+    /// Each row's stretches between cubes, as `(mask, first bit, bit past the end)`.
+    stretches: Vec<Vec<(u128, u32, u32)>>,
+    rocks: Rocks,
+}
+
+/// The lowest `n` bits.
+fn ones(n: u32) -> u128 {
+    u128::MAX.checked_shr(MAX_WIDTH as u32 - n).unwrap_or(0)
+}
+
+impl Platform {
+    fn height(&self) -> usize {
+        self.cubes.len()
+    }
+
+    /// Rolls every rock as far as it goes. North and south, a row's rocks all climb past the
+    /// free rows in front of them at once; west and east, the rocks of each stretch between
+    /// cubes are counted and packed against its end.
+    fn tilt(&self, rocks: &mut Rocks, tilt: Tilt) {
+        let rows = &mut rocks.0;
+        let h = self.height();
+        match tilt {
+            Tilt::North | Tilt::South => {
+                // row `i` counted from the side the rocks roll to
+                let at = |i: usize| if tilt == Tilt::North { i } else { h - 1 - i };
+                for i in 1..h {
+                    let mut moving = std::mem::take(&mut rows[at(i)]);
+                    let mut j = i;
+                    while j > 0 && moving != 0 {
+                        let free = !(rows[at(j - 1)] | self.cubes[at(j - 1)]);
+                        rows[at(j)] |= moving & !free;
+                        moving &= free;
+                        j -= 1;
+                    }
+                    rows[at(j)] |= moving;
+                }
+            }
+            Tilt::West | Tilt::East => {
+                for (row, stretches) in rows.iter_mut().zip(&self.stretches) {
+                    let mut out = 0;
+                    for &(mask, start, end) in stretches {
+                        let n = (*row & mask).count_ones();
+                        if n > 0 {
+                            out |= ones(n) << if tilt == Tilt::West { start } else { end - n };
+                        }
+                    }
+                    *row = out;
+                }
+            }
+        }
+    }
+
+    fn spin(&self, rocks: &mut Rocks) {
+        for tilt in SPIN {
+            self.tilt(rocks, tilt);
+        }
+    }
+
+    fn north_load(&self, rocks: &Rocks) -> u32 {
+        let h = self.height() as u32;
+        rocks
+            .0
+            .iter()
+            .zip(0..)
+            .map(|(row, y)| row.count_ones() * (h - y))
+            .sum()
+    }
+
+    fn grid(&self, rocks: &Rocks) -> Grid {
+        let mut grid = Grid::filled(self.width, self.height(), '.');
+        for p in grid.positions().collect::<Vec<_>>() {
+            let bit = 1 << p.x;
+            let y = p.y as usize;
+            if let Some(c) = grid.get_mut(p) {
+                if self.cubes[y] & bit != 0 {
+                    *c = '#';
+                } else if rocks.0[y] & bit != 0 {
+                    *c = 'O';
+                }
+            }
+        }
+        grid
+    }
+}
+
+/// Where the platform starts repeating under spin cycles.
+#[must_use]
+pub fn spin_cycles(platform: &Platform) -> Cycle<Rocks, impl Fn(&mut Rocks) + '_> {
+    Cycle::hashed(platform.rocks.clone(), |rocks| platform.spin(rocks))
+}
+
+pub fn parse(input: &str) -> Result<Platform> {
+    let grid = input.parse::<Grid>()?;
+    if grid.width() > MAX_WIDTH {
+        return Err(format!("platform is {} wide, at most {MAX_WIDTH} fit", grid.width()).into());
+    }
+
+    let mut cubes = vec![0u128; grid.height()];
+    let mut rocks = vec![0; grid.height()];
+    for (y, row) in grid.rows().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            match c {
+                '#' => cubes[y] |= 1 << x,
+                'O' => rocks[y] |= 1 << x,
+                '.' => {}
+                _ => return Err(format!("unexpected {c:?} at row {y}, column {x}").into()),
+            }
+        }
+    }
+    let stretches = cubes
+        .iter()
+        .map(|&row| {
+            let mut stretches = Vec::new();
+            let mut start = 0;
+            while start < grid.width() as u32 {
+                let end = (start + (row >> start).trailing_zeros()).min(grid.width() as u32);
+                if end > start {
+                    stretches.push((ones(end) & !ones(start), start, end));
+                }
+                start = end + 1;
+            }
+            stretches
+        })
+        .collect();
+    Ok(Platform {
+        width: grid.width(),
+        cubes,
+        stretches,
+        rocks: Rocks(rocks),
+    })
+}
+
+pub fn part1(platform: &Platform) -> Result<u32> {
+    let mut rocks = platform.rocks.clone();
+    platform.tilt(&mut rocks, Tilt::North);
+    Ok(platform.north_load(&rocks))
+}
+
+pub fn part2(platform: &Platform) -> Result<u32> {
+    Ok(platform.north_load(&spin_cycles(platform).state_at(1_000_000_000)))
+}
+
+/// Where part two's spin cycles start repeating.
+pub fn notes(platform: &Platform) -> Result<String> {
+    Ok(format!("Platform {}", spin_cycles(platform)))
+}
+
+/// Every tilt of the first few spin cycles, rounded rocks in yellow.
+pub fn render(input: &str) -> Result<Vec<Frame>> {
+    let platform = parse(input)?;
+    let frame = |rocks: &Rocks| {
+        let grid = platform.grid(rocks);
+        let mut frame = Frame::new(&grid);
+        frame.highlight_all(
+            grid.positions().filter(|&p| grid.get(p) == Some('O')),
+            Rgb::YELLOW,
+        );
+        frame
+    };
+
+    let mut rocks = platform.rocks.clone();
+    let mut frames = vec![frame(&rocks)];
+    for tilt in SPIN.into_iter().cycle().take(4 * RENDERED_CYCLES) {
+        platform.tilt(&mut rocks, tilt);
+        frames.push(frame(&rocks));
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Symmetric, D4};
+    use proptest::prelude::*;
+
+    fn example() -> Platform {
+        parse(include_str!("../../tests/examples/14.ex")).unwrap()
+    }
+
+    #[test]
+    fn tilts_match_example() {
+        let platform = example();
+        let mut rocks = platform.rocks.clone();
+        platform.spin(&mut rocks);
+        assert_eq!(
+            platform.grid(&rocks).to_string().trim_end(),
+            concat!(
+                ".....#....\n",
+                "....#...O#\n",
+                "...OO##...\n",
+                ".OO#......\n",
+                ".....OOO#.\n",
+                ".O#...O#.#\n",
+                "....O#....\n",
+                "......OOOO\n",
+                "#...O###..\n",
+                "#..OO#....",
+            )
+        );
+    }
+
+    #[test]
+    fn example_repeats() {
+        let platform = example();
+        let cycle = spin_cycles(&platform);
+        assert_eq!((cycle.mu, cycle.lambda), (3, 7));
+        assert_eq!(cycle.state_at(3), cycle.state_at(10));
+        assert_ne!(cycle.state_at(2), cycle.state_at(9));
+        assert_eq!(platform.north_load(&cycle.state_at(1_000_000_000)), 64);
+        assert_eq!(
+            notes(&platform).unwrap(),
+            "Platform repeats from step 3 every 7 steps"
+        );
+    }
+
+    #[test]
+    fn full_width_rows() {
+        let row = "O.#".repeat(42) + "..";
+        let platform = parse(&format!("{row}\n{}", ".".repeat(128))).unwrap();
+        let mut rocks = platform.rocks.clone();
+        platform.tilt(&mut rocks, Tilt::East);
+        assert_eq!(rocks.0[0].count_ones(), 42);
+        assert_eq!(rocks.0[0] >> 126, 0);
+        assert_eq!(rocks.0[0] & 0b111, 0b010);
+        platform.tilt(&mut rocks, Tilt::South);
+        let east = (0..42).map(|i| 1 << (3 * i + 1)).sum::<u128>();
+        assert_eq!(rocks.0, [0, east]);
+        assert!(parse(&".".repeat(129)).is_err());
+    }
+
+    // The bitboard tilts each way without turning the platform, so the D4 transforms only
+    // show up here, turning the map to check every tilt against a plain tilt north.
+    proptest! {
+        #[test]
+        fn tilts_agree_with_turning_north(map in "([.#O]{7}\n){5}") {
+            let platform = parse(&map).unwrap();
+            for (tilt, turn) in [
+                (Tilt::North, D4::Identity),
+                (Tilt::West, D4::RotateCw),
+                (Tilt::South, D4::Rotate180),
+                (Tilt::East, D4::RotateCcw),
+            ] {
+                let mut rocks = platform.rocks.clone();
+                platform.tilt(&mut rocks, tilt);
+
+                let mut turned = platform.grid(&platform.rocks);
+                turned.apply(turn);
+                let turned = parse(&turned.to_string()).unwrap();
+                let mut turned_rocks = turned.rocks.clone();
+                turned.tilt(&mut turned_rocks, Tilt::North);
+                let mut expected = turned.grid(&turned_rocks);
+                expected.apply(turn.inverse());
+
+                prop_assert_eq!(platform.grid(&rocks), expected, "{:?}", tilt);
+            }
+        }
+    }
+}
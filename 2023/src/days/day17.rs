@@ -0,0 +1,195 @@
+use crate::render::{Frame, Rgb};
+use crate::{Grid, Result};
+use aoc_2dmap::prelude::Pos;
+use aoc_dijsktra::{Dijsktra, GameState, Path, Transform};
+
+type Direction = Pos;
+
+// East, South, West, North
+const OFFSETS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+fn is_opposite(dir: Direction, to: Direction) -> bool {
+    dir.x == -to.x && dir.y == -to.y
+}
+
+#[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Copy, Clone, Default, Debug)]
+pub struct State {
+    pub cur: Pos,
+    /// Unit step of the last move, zero at the start.
+    pub direction: Direction,
+}
+
+/// Going straight to `to`, losing `cost` heat on the way.
+#[derive(Clone, Debug)]
+pub struct Move {
+    pub to: Pos,
+    pub cost: u32,
+}
+
+impl GameState<LavaCtx<'_>, u32> for State {
+    type Steps = Vec<Move>;
+
+    fn accept(&self, _cost: u32, ctx: &mut LavaCtx) -> bool {
+        self.cur == ctx.goal
+    }
+
+    fn steps(&self, ctx: &mut LavaCtx) -> Self::Steps {
+        // two turns, or all four ways at the start, up to `max_straight` blocks each
+        let mut steps = Vec::with_capacity(4 * ctx.max_straight.max(0) as usize);
+        for o in &OFFSETS {
+            let o = Pos::from(*o);
+            if is_opposite(o, self.direction) || o == self.direction {
+                continue;
+            }
+            let mut cost = 0;
+            for dist in 1..=ctx.max_straight {
+                let to = self.cur + Pos::new(o.x * dist, o.y * dist);
+
+                if let Some(step_cost) = ctx.map.get(to) {
+                    cost += step_cost;
+                    if ctx.min_straight.is_some_and(|m| dist < m) {
+                        continue;
+                    }
+                    steps.push(Move { to, cost });
+                }
+            }
+        }
+        steps
+    }
+
+    /// Every block left to the goal loses at least the least heat of any block.
+    fn heuristic(&self, ctx: &mut LavaCtx) -> u32 {
+        let left =
+            (ctx.goal.x - self.cur.x).unsigned_abs() + (ctx.goal.y - self.cur.y).unsigned_abs();
+        left * ctx.min_heat
+    }
+}
+
+impl Transform<State, u32> for Move {
+    fn cost(&self) -> u32 {
+        self.cost
+    }
+
+    fn transform(&self, state: &State) -> State {
+        State {
+            cur: self.to,
+            direction: (self.to - state.cur).signum(),
+        }
+    }
+}
+
+pub struct LavaCtx<'a> {
+    map: &'a Grid<u32>,
+    goal: Pos,
+    min_heat: u32,
+    min_straight: Option<i32>,
+    max_straight: i32,
+}
+
+/// The cheapest route through the city, turning after `min_straight..=max_straight` blocks.
+pub type Route = Path<State, Move, u32>;
+
+#[must_use]
+pub fn best_route(map: &Grid<u32>, min_straight: Option<i32>, max_straight: i32) -> Option<Route> {
+    // an empty map has no blocks, and no corner to head for either
+    let min_heat = map.positions().filter_map(|p| map.get(p)).min()?;
+    let goal = (map.width() - 1, map.height() - 1).into();
+
+    State::default().shortest_path(&mut LavaCtx {
+        map,
+        goal,
+        min_heat,
+        min_straight,
+        max_straight,
+    })
+}
+
+/// Every block the route enters, in order.
+#[must_use]
+pub fn route_blocks(route: &Route) -> Vec<Pos> {
+    let mut blocks = Vec::new();
+    for (from, m) in route.states.iter().zip(&route.moves) {
+        let dir = (m.to - from.cur).signum();
+        let mut cur = from.cur;
+        while cur != m.to {
+            cur += dir;
+            blocks.push(cur);
+        }
+    }
+    blocks
+}
+
+pub fn parse(input: &str) -> Result<Grid<u32>> {
+    let map = input.parse::<Grid<u32>>()?;
+    if map.height() == 0 {
+        return Err("empty input".into());
+    }
+    Ok(map)
+}
+
+pub fn part1(map: &Grid<u32>) -> Result<u32> {
+    Ok(best_route(map, None, 3).ok_or("failed p1")?.cost)
+}
+
+pub fn part2(map: &Grid<u32>) -> Result<u32> {
+    Ok(best_route(map, Some(4), 10).ok_or("failed p2")?.cost)
+}
+
+/// The part one route, then the part two one, in red.
+pub fn render(input: &str) -> Result<Vec<Frame>> {
+    let map = parse(input)?;
+    [(None, 3), (Some(4), 10)]
+        .into_iter()
+        .map(|(min, max)| {
+            let route = best_route(&map, min, max).ok_or("no route")?;
+            let mut frame = Frame::new(&map);
+            frame
+                .highlight(Pos::new(0, 0), Rgb::RED)
+                .highlight_all(route_blocks(&route), Rgb::RED);
+            Ok(frame)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_map() {
+        assert!(parse("").is_err());
+        assert!(best_route(&Grid::filled(0, 0, 1), None, 3).is_none());
+    }
+
+    #[test]
+    fn example_route_adds_up() {
+        let map = parse(include_str!("../../tests/examples/17.ex")).unwrap();
+        for (min, max, cost) in [(None, 3, 102), (Some(4), 10, 94)] {
+            let route = best_route(&map, min, max).unwrap();
+            assert_eq!(route.cost, cost);
+            assert_eq!(route.states.len(), route.moves.len() + 1);
+            assert_eq!(route.states[0].cur, Pos::new(0, 0));
+            assert_eq!(route.states.last().unwrap().cur, Pos::new(12, 12));
+
+            let blocks = route_blocks(&route);
+            let heat = blocks.iter().map(|&p| map.get(p).unwrap()).sum::<u32>();
+            assert_eq!(heat, route.cost);
+
+            for (from, m) in route.states.iter().zip(&route.moves) {
+                let dist = (m.to.x - from.cur.x).abs() + (m.to.y - from.cur.y).abs();
+                assert!(min.unwrap_or(1) <= dist && dist <= max);
+            }
+            for pair in route.states.windows(2) {
+                assert_ne!(pair[0].direction, pair[1].direction);
+            }
+        }
+    }
+
+    #[test]
+    fn long_straights() {
+        let map = parse(include_str!("../../tests/examples/17.ex")).unwrap();
+        let costs = [3, 10, 15, 40].map(|max| best_route(&map, None, max).unwrap().cost);
+        assert!(costs.windows(2).all(|w| w[1] <= w[0]), "{costs:?}");
+        assert!(best_route(&map, Some(13), 40).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::Result;
+
+type Pt = (i64, i64);
+
+fn p1_extract(s: &str) -> Result<Step> {
+    let mut words = s.split_whitespace();
+
+    Ok((
+        match words.next().ok_or("no direction")? {
+            "R" => (1, 0),
+            "D" => (0, 1),
+            "L" => (-1, 0),
+            "U" => (0, -1),
+            other => return Err(format!("invalid direction: {other}").into()),
+        },
+        words.next().ok_or("no distance")?.parse::<i64>()?,
+    ))
+}
+
+fn p2_extract(s: &str) -> Result<Step> {
+    let w = s.split_whitespace().nth(2).ok_or("no color")?;
+    let dist = i64::from_str_radix(w.get(2..=6).ok_or("bad hex")?, 16)?;
+    let dir = match w.chars().nth(7).ok_or("bad hex")? {
+        '0' => (1, 0),
+        '1' => (0, 1),
+        '2' => (-1, 0),
+        '3' => (0, -1),
+        other => return Err(format!("invalid direction: {other}").into()),
+    };
+    Ok((dir, dist))
+}
+
+// Shoelace theorem, there is no escaping
+fn shoelace(vertices: &[Pt]) -> i64 {
+    assert!(vertices.len() >= 3);
+
+    let a0 = vertices[0].1 * (vertices[vertices.len() - 1].0 - vertices[1].0);
+
+    (a0 + (1..vertices.len() - 1)
+        .map(|i| vertices[i].1 * (vertices[i - 1].0 - vertices[i + 1].0))
+        .sum::<i64>())
+        / 2
+}
+
+/// A dig instruction: direction and distance.
+type Step = (Pt, i64);
+
+fn lagoon_size(steps: impl Iterator<Item = Step>) -> Result<i64> {
+    let mut cur: Pt = (0, 0);
+    let mut vx = vec![cur];
+    let mut diameter = 0;
+
+    for (dir, dist) in steps {
+        let end = (cur.0 + dir.0 * dist, cur.1 + dir.1 * dist);
+        vx.push(end);
+        diameter += dist;
+        cur = end;
+    }
+
+    if vx.len() < 3 {
+        return Err("need at least two dig instructions".into());
+    }
+
+    Ok(shoelace(&vx) + diameter / 2 + 1)
+}
+
+/// Each line read both ways: as written and decoded from its color.
+pub fn parse(input: &str) -> Result<Vec<(Step, Step)>> {
+    input
+        .lines()
+        .map(|line| Ok((p1_extract(line)?, p2_extract(line)?)))
+        .collect()
+}
+
+pub fn part1(plan: &[(Step, Step)]) -> Result<i64> {
+    lagoon_size(plan.iter().map(|x| x.0))
+}
+
+pub fn part2(plan: &[(Step, Step)]) -> Result<i64> {
+    lagoon_size(plan.iter().map(|x| x.1))
+}
@@ -0,0 +1,207 @@
+use crate::{HyperRect, Interval, Result};
+use aoc_prelude::{ArrayVec, HashMap};
+
+type Prop = usize;
+
+// x,m,a,s
+type Rating = [u32; 4];
+type RatingRange = HyperRect<u32, 4>;
+
+/// Each rating goes from 1 to 4000.
+const RATING: Interval<u32> = Interval {
+    start: 1,
+    end: 4001,
+};
+const INIT_RANGE: RatingRange = HyperRect([RATING; 4]);
+const START: &str = "in";
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Comp {
+    None,
+    Less(Prop, u32),
+    Great(Prop, u32),
+}
+
+impl Comp {
+    /// The ratings that pass the comparison, and those left for the next rule.
+    fn split(&self, r: RatingRange) -> (Option<RatingRange>, Option<RatingRange>) {
+        match *self {
+            Comp::None => (Some(r), None),
+            Comp::Less(prop, val) => r.split(prop, val),
+            Comp::Great(prop, val) => {
+                let (rest, pass) = r.split(prop, val + 1);
+                (pass, rest)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct RulePart<'a> {
+    comp: Comp,
+    dest_name: &'a str,
+}
+
+const MAX_RULES: usize = 16;
+
+type RuleSet<'a> = HashMap<&'a str, ArrayVec<RulePart<'a>, MAX_RULES>>;
+
+/// A rule such as `a<2006:qkq`, or a bare destination for the last one.
+fn parse_rule(rule: &str) -> Result<RulePart<'_>> {
+    let Some((cond, dest_name)) = rule.split_once(':') else {
+        return Ok(RulePart {
+            comp: Comp::None,
+            dest_name: rule,
+        });
+    };
+    let (at, op) = cond
+        .char_indices()
+        .find(|&(_, c)| c == '<' || c == '>')
+        .ok_or_else(|| format!("expected < or > in rule: {rule}"))?;
+    let (name, val) = (&cond[..at], &cond[at + 1..]);
+    let prop = match name {
+        "x" => 0,
+        "m" => 1,
+        "a" => 2,
+        "s" => 3,
+        _ => return Err(format!("unknown rating {name:?} in rule: {rule}").into()),
+    };
+    let val = val
+        .parse::<u32>()
+        .map_err(|e| format!("invalid value in rule {rule}: {e}"))?;
+
+    let comp = if op == '<' {
+        Comp::Less(prop, val)
+    } else {
+        Comp::Great(prop, val)
+    };
+    Ok(RulePart { comp, dest_name })
+}
+
+fn extract_nums<const M: usize>(s: &str) -> Result<[u32; M]> {
+    let mut res = [0; M];
+    for (i, w) in s
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|w| !w.is_empty())
+        .enumerate()
+    {
+        *res.get_mut(i)
+            .ok_or_else(|| format!("too many ratings: {s}"))? = w.parse()?;
+    }
+    Ok(res)
+}
+
+fn is_valid_rating(rating: &Rating, valid_ranges: &[RatingRange]) -> bool {
+    valid_ranges.iter().any(|r| r.contains(rating))
+}
+
+fn is_destination<S: AsRef<str>>(s: S) -> bool {
+    s.as_ref().ends_with('A')
+}
+
+fn get_ranges_2(rule_set: &RuleSet) -> Result<ArrayVec<RatingRange, 1024>> {
+    let mut r_ranges = ArrayVec::new();
+    let mut q = ArrayVec::<_, 64>::new();
+    q.push((START, INIT_RANGE));
+
+    while let Some((workflow, ranges)) = q.pop() {
+        if workflow == "R" {
+            continue;
+        }
+        if is_destination(workflow) {
+            r_ranges.push(ranges);
+            continue;
+        }
+
+        let mut outer_range = Some(ranges);
+        let rules = rule_set
+            .get(workflow)
+            .ok_or_else(|| format!("unknown workflow: {workflow}"))?;
+        for rule in rules {
+            let Some(range) = outer_range else {
+                break;
+            };
+            let (true_range, rest) = rule.comp.split(range);
+            if let Some(true_range) = true_range {
+                q.push((rule.dest_name, true_range));
+            }
+            outer_range = rest;
+        }
+    }
+    Ok(r_ranges)
+}
+
+pub struct System {
+    /// Every combination of ratings the workflows accept.
+    accepted: ArrayVec<RatingRange, 1024>,
+    ratings: Vec<Rating>,
+}
+
+pub fn parse(input: &str) -> Result<System> {
+    let (workflows, ratings) = input
+        .split_once("\n\n")
+        .ok_or("expected workflows and ratings")?;
+
+    let mut rules = RuleSet::with_capacity(1024);
+
+    for l in workflows.lines() {
+        let (name, rest) = l
+            .split_once('{')
+            .ok_or_else(|| format!("invalid workflow: {l}"))?;
+        let mut rule = ArrayVec::new();
+        for part in rest
+            .strip_suffix('}')
+            .ok_or_else(|| format!("invalid workflow: {l}"))?
+            .split(',')
+        {
+            rule.try_push(parse_rule(part)?)
+                .map_err(|_| format!("more than {MAX_RULES} rules in workflow: {l}"))?;
+        }
+        rules.insert(name, rule);
+    }
+
+    Ok(System {
+        accepted: get_ranges_2(&rules)?,
+        ratings: ratings.lines().map(extract_nums).collect::<Result<_>>()?,
+    })
+}
+
+pub fn part1(system: &System) -> Result<u32> {
+    Ok(system
+        .ratings
+        .iter()
+        .filter(|r| is_valid_rating(r, &system.accepted))
+        .flatten()
+        .sum::<u32>())
+}
+
+pub fn part2(system: &System) -> Result<u64> {
+    let combinations = system.accepted.iter().map(HyperRect::volume).sum::<u128>();
+    Ok(u64::try_from(combinations)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(workflow: &str) -> String {
+        match parse(&format!("{workflow}\n\n{{x=1,m=2,a=3,s=4}}")) {
+            Ok(_) => panic!("{workflow} parsed"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn malformed_rules() {
+        assert!(parse("in{x<5:R,s>9:A,R}\n\n{x=1,m=2,a=3,s=4}").is_ok());
+        assert_eq!(error("in{b<5:A,R}"), r#"unknown rating "b" in rule: b<5:A"#);
+        assert_eq!(
+            error("in{xm<5:A,R}"),
+            r#"unknown rating "xm" in rule: xm<5:A"#
+        );
+        assert_eq!(error("in{x=5:A,R}"), "expected < or > in rule: x=5:A");
+        assert!(error("in{x<five:A,R}").starts_with("invalid value in rule x<five:A: "));
+        let many = "x<1:R,".repeat(MAX_RULES);
+        assert!(error(&format!("in{{{many}A}}")).starts_with("more than 16 rules"));
+    }
+}
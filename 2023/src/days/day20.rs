@@ -0,0 +1,250 @@
+use crate::{export, Result};
+use aoc_prelude::{ArrayVec, HashMap};
+use std::collections::VecDeque;
+
+const MAX_MODULES: usize = 1024;
+
+#[derive(Clone)]
+enum GateKind {
+    Broadcast,
+    Conj,
+    FlipFLop,
+}
+impl From<&str> for GateKind {
+    fn from(s: &str) -> Self {
+        if s.starts_with('%') {
+            Self::FlipFLop
+        } else if s.starts_with('&') {
+            Self::Conj
+        } else {
+            Self::Broadcast
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Gate {
+    kind: GateKind,
+    out: ArrayVec<usize, 32>,
+}
+
+impl Default for Gate {
+    fn default() -> Self {
+        Self {
+            kind: GateKind::FlipFLop,
+            out: ArrayVec::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct StackMap<T, const M: usize> {
+    inner: ArrayVec<T, M>,
+    is_set: ArrayVec<bool, M>,
+}
+
+impl<const M: usize, T: Default> StackMap<T, M> {
+    fn new() -> Self {
+        Self {
+            inner: ArrayVec::from_iter((0..M).map(|_| T::default())),
+            is_set: ArrayVec::from([false; M]),
+        }
+    }
+
+    fn get(&self, k: usize) -> &T {
+        &self.inner[k]
+    }
+
+    fn get_mut(&mut self, k: usize) -> &mut T {
+        &mut self.inner[k]
+    }
+
+    fn contains_key(&self, k: usize) -> bool {
+        self.is_set[k]
+    }
+
+    fn set(&mut self, k: usize, val: T) {
+        self.is_set[k] = true;
+        self.inner[k] = val;
+    }
+}
+
+/// Hands out dense ids to module names, in order of appearance.
+#[derive(Default)]
+struct Names<'a>(HashMap<&'a str, usize>);
+
+impl<'a> Names<'a> {
+    fn id(&mut self, name: &'a str) -> Result<usize> {
+        let next = self.0.len();
+        let id = *self.0.entry(name).or_insert(next);
+        if id < MAX_MODULES {
+            Ok(id)
+        } else {
+            Err(format!("more than {MAX_MODULES} modules").into())
+        }
+    }
+}
+
+type Circuit = StackMap<Gate, MAX_MODULES>;
+type Rev = StackMap<ArrayVec<usize, 32>, MAX_MODULES>;
+type State = StackMap<bool, MAX_MODULES>;
+
+#[derive(Clone)]
+pub struct World {
+    circuit: Circuit,
+    rev: Rev,
+    state: State,
+    rx_cycles: ArrayVec<usize, 16>,
+    rx_inputs: StackMap<(), MAX_MODULES>,
+    /// Number of conjunctions feeding into rx, if there is an rx.
+    expected_cycles: Option<usize>,
+    broadcaster: usize,
+    /// Module names, by id.
+    names: Vec<String>,
+}
+
+impl World {
+    fn tick(&mut self, q_buf: &mut VecDeque<(usize, usize, bool)>, t: usize) -> (usize, usize) {
+        let (mut lo, mut hi) = (1, 0);
+        q_buf.clear();
+        for b_node in &self.circuit.get(self.broadcaster).out {
+            q_buf.push_back((self.broadcaster, *b_node, false));
+        }
+
+        while let Some((inp, out, pulse)) = q_buf.pop_front() {
+            if pulse {
+                hi += 1;
+            } else {
+                lo += 1;
+            }
+            if !self.circuit.contains_key(out) {
+                continue;
+            }
+            self.state.set(inp, pulse);
+            let out_gate = self.circuit.get(out);
+            match out_gate.kind {
+                GateKind::FlipFLop if !pulse => {
+                    let state = self.state.get(out);
+                    for downstream in &out_gate.out {
+                        q_buf.push_back((out, *downstream, !state));
+                    }
+                    self.state.set(out, !state);
+                }
+                GateKind::Conj => {
+                    let send = !self.rev.get(out).iter().all(|&x| *self.state.get(x));
+                    if send && self.rx_inputs.contains_key(out) {
+                        self.rx_cycles.push(t);
+                    }
+                    for downstream in &out_gate.out {
+                        q_buf.push_back((out, *downstream, send));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (lo, hi)
+    }
+}
+
+pub fn parse(input: &str) -> Result<World> {
+    let mut circuit = Circuit::new();
+    let mut rev = Rev::new();
+    let mut state = State::new();
+    let mut names = Names::default();
+
+    for l in input.lines() {
+        let (name, rest) = l
+            .split_once(" -> ")
+            .ok_or_else(|| format!("invalid module: {l}"))?;
+        let out = rest
+            .split(", ")
+            .map(|x| names.id(x))
+            .collect::<Result<ArrayVec<_, 32>>>()?;
+        let kind = GateKind::from(name);
+        let real_name = if matches!(kind, GateKind::Broadcast) {
+            names.id(name)?
+        } else {
+            names.id(name.split_at(1).1)?
+        };
+        state.set(real_name, false);
+        for output in &out {
+            rev.get_mut(*output).push(real_name);
+        }
+        circuit.set(real_name, Gate { kind, out });
+    }
+
+    let broadcaster = *names.0.get("broadcaster").ok_or("no broadcaster")?;
+
+    let (mut rx_inputs, rx_cycles) = (StackMap::new(), ArrayVec::new());
+
+    // rx is fed by a single conjunction, which in turn is fed by a few cycles
+    let feeder = names.0.get("rx").and_then(|&rx| rev.get(rx).first());
+    let expected_cycles = feeder.map(|&feeder| {
+        rev.get(feeder).iter().for_each(|&x| rx_inputs.set(x, ()));
+        rev.get(feeder).len()
+    });
+
+    let mut by_id = names.0.into_iter().collect::<Vec<_>>();
+    by_id.sort_unstable_by_key(|&(_, id)| id);
+
+    Ok(World {
+        circuit,
+        rev,
+        state,
+        rx_cycles,
+        rx_inputs,
+        expected_cycles,
+        broadcaster,
+        names: by_id.into_iter().map(|(name, _)| name.to_owned()).collect(),
+    })
+}
+
+pub fn part1(world: &World) -> Result<usize> {
+    let mut world = world.clone();
+    let mut q_buf = VecDeque::new();
+    let (lo, hi) = (1..=1000)
+        .map(|t| world.tick(&mut q_buf, t))
+        .fold((0, 0), |acc, x| (acc.0 + x.0, acc.1 + x.1));
+    Ok(lo * hi)
+}
+
+pub fn part2(world: &World) -> Result<usize> {
+    let expected_cycles = world.expected_cycles.ok_or("nothing feeds rx")?;
+    let mut world = world.clone();
+    let mut q_buf = VecDeque::new();
+    for t in 1.. {
+        if world.rx_cycles.len() == expected_cycles {
+            break;
+        }
+        world.tick(&mut q_buf, t);
+    }
+
+    Ok(world.rx_cycles.iter().product::<usize>())
+}
+
+/// The circuit, with the conjunctions whose cycles part two multiplies highlighted.
+pub fn graph(world: &World) -> Result<export::Graph> {
+    let mut graph = export::Graph::directed();
+    for (id, name) in world.names.iter().enumerate() {
+        let label = match world
+            .circuit
+            .contains_key(id)
+            .then(|| &world.circuit.get(id).kind)
+        {
+            Some(GateKind::FlipFLop) => format!("%{name}"),
+            Some(GateKind::Conj) => format!("&{name}"),
+            Some(GateKind::Broadcast) | None => name.clone(),
+        };
+        graph.node(name, label);
+        if world.rx_inputs.contains_key(id) {
+            graph.highlight_node(name);
+        }
+        if world.circuit.contains_key(id) {
+            for &out in &world.circuit.get(id).out {
+                graph.edge(name, &world.names[out], None);
+            }
+        }
+    }
+    Ok(graph)
+}
@@ -0,0 +1,475 @@
+use crate::render::{Frame, Rgb};
+use crate::{Grid, Result};
+use aoc_2dmap::prelude::Pos;
+use aoc_prelude::{num_integer, HashMap};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+const UNREACHED: u32 = u32::MAX;
+
+/// Distances within one copy of the map, counted from its closest entry.
+struct Pattern {
+    dist: Vec<u32>,
+    /// `plots[p][d]`: cells at most `d` away, at an even (`p = 0`) or odd distance.
+    plots: [Vec<u64>; 2],
+}
+
+impl Pattern {
+    fn new(dist: Vec<u32>) -> Self {
+        let reached = || dist.iter().copied().filter(|&d| d != UNREACHED);
+        let max = reached().max().unwrap_or(0);
+        let mut plots = [vec![0; max as usize + 1], vec![0; max as usize + 1]];
+        for d in reached() {
+            plots[d as usize % 2][d as usize] += 1;
+        }
+        for parity in &mut plots {
+            for d in 1..parity.len() {
+                parity[d] += parity[d - 1];
+            }
+        }
+        Self { dist, plots }
+    }
+
+    /// Cells exactly `steps` away or closer by an even number.
+    fn plots(&self, steps: u32) -> u64 {
+        let parity = &self.plots[steps as usize % 2];
+        parity[(steps as usize).min(parity.len() - 1)]
+    }
+}
+
+/// One copy of the map on the infinite tiling.
+struct Tile {
+    /// What the neighbours handed over at the border, see [`Reach::entry`].
+    entry: Vec<u32>,
+    pattern: usize,
+    /// Distance of the closest entry, which the pattern counts from.
+    offset: u32,
+}
+
+/// Shortest distances from the start on the infinitely tiled map, worked out one copy of the
+/// map at a time and only as far as asked.
+///
+/// A tile's distances follow from those its neighbours hand over at its border, so tiles are
+/// settled in order of their closest entry and revisited if a neighbour later offers a shorter
+/// way in. Tiles entered the same way up to a constant share their distances: away from the
+/// start, most of them are a lookup instead of a BFS.
+struct Reach {
+    /// Whether each cell, row by row, is a rock.
+    rocks: Vec<bool>,
+    width: usize,
+    height: usize,
+    start: usize,
+    /// For each neighbour, the cells on the side facing it and the cells across the border.
+    sides: [(Pos, Vec<(usize, usize)>); 4],
+    patterns: Vec<Pattern>,
+    by_entry: HashMap<Vec<u32>, usize>,
+    tiles: HashMap<Pos, Tile>,
+    /// Tiles to revisit, by the shortest distance a neighbour offered them.
+    queue: BinaryHeap<Reverse<(u32, Pos)>>,
+}
+
+impl Reach {
+    fn new(map: &Grid, start: Pos) -> Self {
+        let (w, h) = (map.width(), map.height());
+        let sides = [
+            (
+                Pos::new(1, 0),
+                (0..h).map(|y| (y * w + w - 1, y * w)).collect(),
+            ),
+            (
+                Pos::new(-1, 0),
+                (0..h).map(|y| (y * w, y * w + w - 1)).collect(),
+            ),
+            (
+                Pos::new(0, 1),
+                (0..w).map(|x| ((h - 1) * w + x, x)).collect(),
+            ),
+            (
+                Pos::new(0, -1),
+                (0..w).map(|x| (x, (h - 1) * w + x)).collect(),
+            ),
+        ];
+        let mut reach = Self {
+            rocks: map.positions().map(|p| map.get(p) == Some('#')).collect(),
+            width: w,
+            height: h,
+            start: start.y as usize * w + start.x as usize,
+            sides,
+            patterns: Vec::new(),
+            by_entry: HashMap::new(),
+            tiles: HashMap::new(),
+            queue: BinaryHeap::new(),
+        };
+        reach.queue.push(Reverse((0, Pos::default())));
+        reach
+    }
+
+    fn dist(&self, tile: Pos, cell: usize) -> u32 {
+        self.tiles.get(&tile).map_or(UNREACHED, |t| {
+            let d = self.patterns[t.pattern].dist[cell];
+            if d == UNREACHED {
+                d
+            } else {
+                d + t.offset
+            }
+        })
+    }
+
+    /// For each side in turn, the distance of each of its cells through the neighbour beyond.
+    fn entry(&self, tile: Pos) -> Vec<u32> {
+        self.sides
+            .iter()
+            .flat_map(|(dir, cells)| {
+                cells.iter().map(move |&(here, there)| {
+                    if self.rocks[here] {
+                        UNREACHED
+                    } else {
+                        self.dist(tile + *dir, there).saturating_add(1)
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Multi-source BFS within one tile, sources joining once the BFS gets to their distance.
+    fn distances(&self, mut seeds: Vec<(u32, usize)>) -> Vec<u32> {
+        let (width, height) = (self.width, self.height);
+        seeds.sort_unstable();
+        let mut seeds = seeds.into_iter().peekable();
+        let mut dist = vec![UNREACHED; width * height];
+        let mut queue = VecDeque::new();
+        loop {
+            let from_seed = match (queue.front(), seeds.peek()) {
+                (Some(&(queued, _)), Some(&(seed, _))) => seed < queued,
+                (queued, seed) => queued.is_none() && seed.is_some(),
+            };
+            let next = if from_seed {
+                seeds.next()
+            } else {
+                queue.pop_front()
+            };
+            let Some((d, cell)) = next else {
+                break;
+            };
+            if from_seed {
+                if d >= dist[cell] {
+                    continue;
+                }
+                dist[cell] = d;
+            } else if d > dist[cell] {
+                continue;
+            }
+
+            let (x, y) = (cell % width, cell / width);
+            let neighbors = [
+                (x > 0).then(|| cell - 1),
+                (x + 1 < width).then(|| cell + 1),
+                (y > 0).then(|| cell - width),
+                (y + 1 < height).then(|| cell + width),
+            ];
+            for next in neighbors.into_iter().flatten() {
+                if d + 1 < dist[next] && !self.rocks[next] {
+                    dist[next] = d + 1;
+                    queue.push_back((d + 1, next));
+                }
+            }
+        }
+        dist
+    }
+
+    /// Settles every tile with an entry at most `depth` away.
+    fn explore(&mut self, depth: u32) {
+        while let Some(&Reverse((key, tile))) = self.queue.peek() {
+            if key > depth {
+                break;
+            }
+            self.queue.pop();
+
+            let entry = self.entry(tile);
+            if self.tiles.get(&tile).is_some_and(|t| t.entry == entry) {
+                continue;
+            }
+            let seeds = self.sides.iter().flat_map(|(_, cells)| cells).map(|c| c.0);
+            let mut seeds = entry.iter().copied().zip(seeds).collect::<Vec<_>>();
+            let (pattern, offset) = if tile == Pos::default() {
+                // the start is inside, so the entry doesn't tell everything
+                seeds.push((0, self.start));
+                self.patterns.push(Pattern::new(self.distances(seeds)));
+                (self.patterns.len() - 1, 0)
+            } else {
+                let offset = entry.iter().copied().min().unwrap_or(UNREACHED);
+                if offset == UNREACHED {
+                    continue;
+                }
+                let key = entry
+                    .iter()
+                    .map(|&d| if d == UNREACHED { d } else { d - offset })
+                    .collect::<Vec<_>>();
+                let pattern = if let Some(&pattern) = self.by_entry.get(&key) {
+                    pattern
+                } else {
+                    let seeds = key.iter().copied().zip(seeds.iter().map(|s| s.1));
+                    let seeds = seeds.filter(|s| s.0 != UNREACHED).collect();
+                    self.patterns.push(Pattern::new(self.distances(seeds)));
+                    self.by_entry.insert(key, self.patterns.len() - 1);
+                    self.patterns.len() - 1
+                };
+                (pattern, offset)
+            };
+            self.tiles.insert(
+                tile,
+                Tile {
+                    entry,
+                    pattern,
+                    offset,
+                },
+            );
+
+            for (dir, cells) in &self.sides {
+                let closest = cells
+                    .iter()
+                    .map(|&(here, _)| self.dist(tile, here).saturating_add(1))
+                    .min()
+                    .unwrap_or(UNREACHED);
+                if closest != UNREACHED {
+                    self.queue.push(Reverse((closest, tile + *dir)));
+                }
+            }
+        }
+    }
+
+    /// Plots reachable in exactly `steps`.
+    fn plots(&mut self, steps: u32) -> u64 {
+        self.explore(steps);
+        self.tiles
+            .values()
+            .filter(|t| t.offset <= steps)
+            .map(|t| self.patterns[t.pattern].plots(steps - t.offset))
+            .sum()
+    }
+}
+
+pub struct Garden {
+    map: Grid,
+    start: Pos,
+    /// Shared by both parts, and explored further whenever one asks for more steps.
+    reach: RefCell<Reach>,
+}
+
+/// Times the second difference has to come out the same before the growth is taken as
+/// quadratic.
+const STABLE_ROUNDS: usize = 2;
+
+/// Map periods to try before giving up on quadratic growth.
+const MAX_PERIODS: u64 = 40;
+
+/// Plots reachable in exactly `n_steps` on the infinitely tiled map.
+///
+/// With `p` the period of the tiling and `r = n_steps % p`, the counts after `r`, `r + p`,
+/// `r + 2p`, ... steps end up growing quadratically once the reachable area covers whole copies
+/// of the map. So they're counted one period at a time until their second difference settles,
+/// and the rest follows from exact finite differences.
+fn reachable(garden: &Garden, n_steps: u64) -> Result<u64> {
+    let period = num_integer::lcm(garden.map.width(), garden.map.height()) as u64;
+    let (r, target) = (n_steps % period, n_steps / period);
+
+    let mut reach = garden.reach.borrow_mut();
+    let mut counts = Vec::<i128>::new();
+    let second_diff = |c: &[i128], k: usize| c[k] - 2 * c[k - 1] + c[k - 2];
+    let mut stable = 0;
+    for k in 0..=target.min(MAX_PERIODS) {
+        let steps = u32::try_from(r + k * period)?;
+        counts.push(reach.plots(steps).into());
+        let k = k as usize;
+        if k >= 3 && second_diff(&counts, k) == second_diff(&counts, k - 1) {
+            stable += 1;
+        } else {
+            stable = 0;
+        }
+        if stable == STABLE_ROUNDS {
+            let (a, d1, d2) = (
+                counts[k],
+                counts[k] - counts[k - 1],
+                second_diff(&counts, k),
+            );
+            let m = i128::from(target) - k as i128;
+            // m * (m + 1) alone can overflow, halve whichever factor is even first
+            let triangle = if m % 2 == 0 {
+                (m / 2).checked_mul(m + 1)
+            } else {
+                m.checked_mul((m + 1) / 2)
+            };
+            return triangle
+                .and_then(|t| d2.checked_mul(t))
+                .and_then(|x| x.checked_add(m.checked_mul(d1)?))
+                .and_then(|x| x.checked_add(a))
+                .and_then(|x| u64::try_from(x).ok())
+                .ok_or_else(|| format!("plots reachable in {n_steps} steps overflow u64").into());
+        }
+    }
+    match counts.get(target as usize) {
+        Some(&count) => Ok(u64::try_from(count)?),
+        None => Err(format!("no quadratic growth within {MAX_PERIODS} periods of the map").into()),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Garden> {
+    let map = input.parse::<Grid>()?;
+
+    let start = map
+        .positions()
+        .find(|&p| map.get(p) == Some('S'))
+        .ok_or("no start")?;
+
+    let reach = RefCell::new(Reach::new(&map, start));
+    Ok(Garden { map, start, reach })
+}
+
+pub fn part1(garden: &Garden) -> Result<u64> {
+    Ok(garden.reach.borrow_mut().plots(64))
+}
+
+pub fn part2(garden: &Garden) -> Result<u64> {
+    reachable(garden, 26_501_365)
+}
+
+/// Plots of the original map reachable in part one's 64 steps in green, the start in red.
+pub fn render(input: &str) -> Result<Vec<Frame>> {
+    let garden = parse(input)?;
+    let mut reach = garden.reach.borrow_mut();
+    reach.explore(64);
+    let plots = garden.map.positions().filter(|p| {
+        let d = reach.dist(Pos::default(), p.y as usize * reach.width + p.x as usize);
+        d <= 64 && d.is_multiple_of(2)
+    });
+
+    let mut frame = Frame::new(&garden.map);
+    frame
+        .highlight_all(plots, Rgb::GREEN)
+        .highlight(garden.start, Rgb::RED);
+    Ok(vec![frame])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_prelude::HashSet;
+    use proptest::prelude::*;
+
+    fn example() -> Garden {
+        parse(include_str!("../../tests/examples/21.ex")).unwrap()
+    }
+
+    /// Plain BFS over `(tile, position)` pairs.
+    fn brute_force(garden: &Garden, steps: u32) -> u64 {
+        let (w, h) = (garden.map.width() as i32, garden.map.height() as i32);
+        let mut seen: HashSet<Pos> = HashSet::from([garden.start]);
+        let mut frontier = vec![garden.start];
+        let mut plots = u64::from(steps.is_multiple_of(2));
+        for step in 1..=steps {
+            let mut next = Vec::new();
+            for p in frontier {
+                for n in p.neighbors_simple() {
+                    let wrapped = Pos::new(n.x.rem_euclid(w), n.y.rem_euclid(h));
+                    if garden.map.get(wrapped) != Some('#') && seen.insert(n) {
+                        next.push(n);
+                    }
+                }
+            }
+            if step % 2 == steps % 2 {
+                plots += next.len() as u64;
+            }
+            frontier = next;
+        }
+        plots
+    }
+
+    #[test]
+    fn example_six_steps() {
+        assert_eq!(example().reach.borrow_mut().plots(6), 16);
+    }
+
+    #[test]
+    fn example_infinite_tiling() {
+        let garden = example();
+        for (steps, plots) in [
+            (6, 16),
+            (10, 50),
+            (50, 1594),
+            (100, 6536),
+            (500, 167_004),
+            (1000, 668_697),
+            (5000, 16_733_044),
+        ] {
+            assert_eq!(reachable(&garden, steps).unwrap(), plots, "{steps} steps");
+        }
+    }
+
+    #[test]
+    fn any_step_count() {
+        assert_eq!(
+            reachable(&example(), u64::MAX).unwrap_err().to_string(),
+            format!("plots reachable in {} steps overflow u64", u64::MAX)
+        );
+        // a single column, open both ways: n + 1 plots after n steps
+        let line = parse("S#").unwrap();
+        assert_eq!(reachable(&line, u64::MAX - 1).unwrap(), u64::MAX);
+        assert!(reachable(&line, u64::MAX).is_err());
+        let walled = parse("S#\n##").unwrap();
+        assert_eq!(reachable(&walled, u64::MAX - 1).unwrap(), 1);
+        assert_eq!(reachable(&walled, u64::MAX).unwrap(), 0);
+    }
+
+    #[test]
+    fn one_traversal_for_all_budgets() {
+        let garden = example();
+        let mut reach = Reach::new(&garden.map, garden.start);
+        for steps in (0..120).rev().step_by(9) {
+            assert_eq!(
+                reach.plots(steps),
+                brute_force(&garden, steps),
+                "{steps} steps"
+            );
+        }
+        // far from the start, tiles repeat the same few patterns
+        assert!(reach.patterns.len() < reach.tiles.len() / 4);
+    }
+
+    #[test]
+    fn parts_share_one_traversal() {
+        let garden = example();
+        part1(&garden).unwrap();
+        let tiles = garden.reach.borrow().tiles.len();
+        part2(&garden).unwrap();
+        let explored = (
+            garden.reach.borrow().tiles.len(),
+            garden.reach.borrow().patterns.len(),
+        );
+        assert!(explored.0 > tiles);
+        // part one's 64 steps are long settled by then, so asking again finds nothing new
+        part1(&garden).unwrap();
+        assert_eq!(
+            (
+                garden.reach.borrow().tiles.len(),
+                garden.reach.borrow().patterns.len()
+            ),
+            explored
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(
+            map in "([.#]{5}\n){4}",
+            start in 0..20usize,
+            steps in 0..40u32,
+        ) {
+            let mut cells = map.into_bytes();
+            let start = start + start / 5;
+            cells[start] = b'S';
+            let garden = parse(std::str::from_utf8(&cells).unwrap()).unwrap();
+            prop_assert_eq!(garden.reach.borrow_mut().plots(steps), brute_force(&garden, steps));
+        }
+    }
+}
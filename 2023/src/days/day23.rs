@@ -0,0 +1,346 @@
+use crate::render::{Frame, Rgb};
+use crate::{export, Grid, Result};
+use aoc_2dmap::prelude::{Map, Pos};
+use aoc_prelude::{ArrayVec, Entry, HashMap, HashSet};
+use std::collections::VecDeque;
+use std::iter::once;
+
+const MAX_NODES: usize = 512;
+type Edges = ArrayVec<(Node, usize), 64>;
+type Graph = ArrayVec<Edges, MAX_NODES>;
+
+fn make_array<const M: usize, T: Default>() -> ArrayVec<T, M> {
+    ArrayVec::<T, M>::from_iter((0..M).map(|_| T::default()))
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+struct Node {
+    id: usize,
+}
+
+/// Numbers the junctions in the order they're met, so sets of them fit in a `u64`.
+#[derive(Default)]
+struct Junctions {
+    ids: HashMap<Pos, usize>,
+    /// Where each junction is, by id.
+    positions: Vec<Pos>,
+}
+
+impl Junctions {
+    fn node(&mut self, pos: Pos) -> Node {
+        let next = self.positions.len();
+        let id = *self.ids.entry(pos).or_insert(next);
+        if id == next {
+            self.positions.push(pos);
+        }
+        Node { id }
+    }
+}
+
+struct World {
+    graph: Graph,
+    start: Node,
+    goal: Node,
+    adj_masks: ArrayVec<u64, MAX_NODES>,
+    junctions: Junctions,
+}
+
+impl World {
+    fn from_map<M: Fn(Pos) -> ArrayVec<Pos, 4>>(map: &Map<char>, make_neighbors: M) -> Self {
+        let mut junctions = Junctions::default();
+        let (start_pos, goal_pos) = (Pos::new(1, 0), map.size - Pos::new(2, 1));
+        let start = junctions.node(start_pos);
+        let goal = junctions.node(goal_pos);
+
+        let mut graph = make_array::<MAX_NODES, Edges>();
+        let mut q = VecDeque::new();
+        let mut seen = HashSet::new();
+
+        for tile in map
+            .iter()
+            .filter(|&p| is_intersection(p, map))
+            .chain(once(start_pos))
+            .chain(once(goal_pos))
+        {
+            q.clear();
+            q.push_back((tile, 0));
+            seen.clear();
+
+            let mut res = Edges::new();
+
+            // BFS from this POI to adjacent POIs and stop there
+            while let Some((cur, t)) = q.pop_front() {
+                if seen.contains(&cur) {
+                    continue;
+                }
+                seen.insert(cur);
+
+                for next in make_neighbors(cur)
+                    .into_iter()
+                    .filter(|&p| is_valid(p, map) && !seen.contains(&p))
+                {
+                    if is_intersection(next, map) || next == goal_pos {
+                        res.push((junctions.node(next), t + 1));
+                    } else {
+                        q.push_back((next, t + 1));
+                    }
+                }
+            }
+            graph[junctions.node(tile).id] = res;
+        }
+
+        // head straight to the goal if within reach
+        graph[goal.id].clone().into_iter().for_each(|(n, cost)| {
+            graph[n.id] = Edges::from_iter([(goal, cost)]);
+        });
+
+        let adj_masks: ArrayVec<u64, MAX_NODES> = graph
+            .iter()
+            .map(|edges| edges.iter().fold(0, |mask, (adj, _)| mask | (1 << adj.id)))
+            .collect();
+
+        World {
+            graph,
+            start,
+            goal,
+            adj_masks,
+            junctions,
+        }
+    }
+
+    // idea stolen from: https://github.com/mr-kaffee/aoc-2023
+    fn reachable(&self, idx: usize, seen: u64) -> u64 {
+        let mut queue = 1u64 << idx;
+        let mut reached = seen | queue;
+
+        while queue != 0 {
+            let cur_idx = queue.trailing_zeros(); // get the idx back
+            queue &= !(1 << cur_idx); // take the current idx out of the queue
+
+            let mask = self.adj_masks[cur_idx as usize]; // 1 for all our neighbors
+            queue |= mask & !reached; // extend queue with neighbors we haven't reached
+            reached |= mask; // mark neighbors as reached
+        }
+
+        reached & !seen // what the BFS reached but we haven't seen yet => reachable
+    }
+}
+
+/// Junctions of the hike being tried, and of the longest one found so far.
+#[derive(Default)]
+struct Route {
+    trail: Vec<Node>,
+    longest: Vec<Node>,
+    length: usize,
+}
+
+fn hike<M: Fn(Pos) -> ArrayVec<Pos, 4>>(map: &Map<char>, make_neighbors: M) -> (World, Route) {
+    let world = World::from_map(map, make_neighbors);
+    let mut route = Route::default();
+    compute_paths(
+        &world,
+        world.start,
+        0,
+        0u64,
+        &mut HashMap::new(),
+        &mut route,
+    );
+    (world, route)
+}
+
+fn longest_hike<M: Fn(Pos) -> ArrayVec<Pos, 4>>(map: &Map<char>, make_neighbors: M) -> usize {
+    hike(map, make_neighbors).1.length
+}
+
+pub fn parse(input: &str) -> Result<Map<char>> {
+    let grid = input.parse::<Grid>()?;
+    if grid.height() == 0 {
+        return Err("empty input".into());
+    }
+    Ok(grid.into())
+}
+
+pub fn part1(map: &Map<char>) -> Result<usize> {
+    Ok(longest_hike(map, |p| make_neighbors_p1(p, map)))
+}
+
+pub fn part2(map: &Map<char>) -> Result<usize> {
+    Ok(longest_hike(map, |p| {
+        ArrayVec::from_iter(p.neighbors_simple())
+    }))
+}
+
+fn compute_paths(
+    world: &World,
+    start: Node,
+    cur_cost: usize,
+    seen: u64,
+    bests: &mut HashMap<(usize, u64), usize>,
+    route: &mut Route,
+) -> usize {
+    if start == world.goal {
+        if cur_cost > route.length {
+            route.length = cur_cost;
+            route.longest = route.trail.iter().copied().chain(once(start)).collect();
+        }
+        return cur_cost;
+    }
+
+    // goal is not reachable
+    let reachable = world.reachable(start.id, seen);
+    if reachable & (1 << world.goal.id) == 0 {
+        return 0;
+    }
+
+    // idea stolen from: https://github.com/mr-kaffee/aoc-2023
+    match bests.entry((start.id, reachable)) {
+        Entry::Occupied(o) if cur_cost <= *o.get() => return 0,
+        Entry::Occupied(mut o) => *o.get_mut() = cur_cost,
+        Entry::Vacant(v) => _ = v.insert(cur_cost),
+    }
+
+    // peel off neighboring nodes and recurse
+    let new_seen = seen | 1_u64 << start.id;
+    route.trail.push(start);
+    let mut res = 0;
+    for &(next, cost) in world.graph[start.id]
+        .iter()
+        .filter(|(n, _)| seen & 1_u64 << n.id == 0)
+    {
+        res = res.max(compute_paths(
+            world,
+            next,
+            cur_cost + cost,
+            new_seen,
+            bests,
+            route,
+        ));
+    }
+    route.trail.pop();
+    res
+}
+
+/// The junctions of the part two graph, labelled by position, with the hike lengths between
+/// them. Start and goal are highlighted.
+pub fn graph(map: &Map<char>) -> Result<export::Graph> {
+    let world = World::from_map(map, |p| ArrayVec::from_iter(p.neighbors_simple()));
+
+    let mut graph = export::Graph::undirected();
+    for (id, pos) in world.junctions.positions.iter().enumerate() {
+        graph.node(id, format!("{},{}", pos.x, pos.y));
+    }
+    // every hike shows up from both ends, except where it got cut short to head for the goal
+    let mut hikes = HashSet::new();
+    for (from, edges) in world.graph.iter().enumerate() {
+        for &(to, cost) in edges {
+            if hikes.insert((from.min(to.id), from.max(to.id))) {
+                graph.edge(from, to.id, Some(cost.to_string()));
+            }
+        }
+    }
+    graph
+        .highlight_node(world.start.id)
+        .highlight_node(world.goal.id);
+    Ok(graph)
+}
+
+/// The cells from junction `from` to junction `to` along the corridor `length` steps long, both
+/// ends included.
+fn corridor(map: &Map<char>, from: Pos, to: Pos, length: usize) -> Option<Vec<Pos>> {
+    from.neighbors_simple()
+        .filter(|&p| is_valid(p, map))
+        .find_map(|first| {
+            let (mut prev, mut cur) = (from, first);
+            let mut cells = vec![from];
+            for _ in 1..length {
+                if is_intersection(cur, map) {
+                    return None;
+                }
+                cells.push(cur);
+                let next = cur
+                    .neighbors_simple()
+                    .find(|&n| n != prev && is_valid(n, map))?;
+                (prev, cur) = (cur, next);
+            }
+            (cur == to).then(|| {
+                cells.push(cur);
+                cells
+            })
+        })
+}
+
+/// Every cell of part two's longest hike, in order.
+fn hike_cells(map: &Map<char>) -> Result<Vec<Pos>> {
+    let (world, route) = hike(map, |p| ArrayVec::from_iter(p.neighbors_simple()));
+    let at = |n: Node| world.junctions.positions[n.id];
+
+    let mut cells = vec![at(world.start)];
+    for leg in route.longest.windows(2) {
+        let length = world.graph[leg[0].id]
+            .iter()
+            .find(|(n, _)| *n == leg[1])
+            .map(|&(_, length)| length)
+            .ok_or("hike along a missing corridor")?;
+        let walk = corridor(map, at(leg[0]), at(leg[1]), length)
+            .ok_or_else(|| format!("no corridor from {:?} to {:?}", at(leg[0]), at(leg[1])))?;
+        cells.extend(walk.into_iter().skip(1));
+    }
+    Ok(cells)
+}
+
+/// The longest hike of part two in red.
+pub fn render(input: &str) -> Result<Vec<Frame>> {
+    let map = parse(input)?;
+    let mut frame = Frame::new(&map);
+    frame.highlight_all(hike_cells(&map)?, Rgb::RED);
+    Ok(vec![frame])
+}
+
+fn make_neighbors_p1(p: Pos, map: &Map<char>) -> ArrayVec<Pos, 4> {
+    let mut ret = ArrayVec::new();
+    match map.get(p) {
+        Some('.') => ret.extend(p.neighbors_simple()),
+        Some('<') => ret.push(p + (-1, 0).into()),
+        Some('>') => ret.push(p + (1, 0).into()),
+        Some('^') => ret.push(p + (0, -1).into()),
+        Some('v') => ret.push(p + (0, 1).into()),
+        _ => {}
+    }
+    ret
+}
+
+fn is_valid(p: Pos, map: &Map<char>) -> bool {
+    matches!(map.get(p), Some(c) if c != '#')
+}
+
+fn is_intersection(p: Pos, map: &Map<char>) -> bool {
+    is_valid(p, map) && p.neighbors_simple().filter(|&p| is_valid(p, map)).count() > 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hike_walks_the_longest_way() {
+        // the graph lives on the stack, more of it than a test thread gets
+        let walk = std::thread::Builder::new()
+            .stack_size(8 << 20)
+            .spawn(|| {
+                let map = parse(include_str!("../../tests/examples/23.ex")).unwrap();
+                let cells = hike_cells(&map).unwrap();
+                assert_eq!(cells.len(), part2(&map).unwrap() + 1);
+                assert_eq!(cells.first(), Some(&Pos::new(1, 0)));
+                assert_eq!(cells.last(), Some(&(map.size - Pos::new(2, 1))));
+                assert_eq!(cells.iter().collect::<HashSet<_>>().len(), cells.len());
+                for step in cells.windows(2) {
+                    assert!(step[0].neighbors_simple().any(|p| p == step[1]), "{step:?}");
+                    assert_ne!(map.get(step[1]), Some('#'));
+                }
+            })
+            .unwrap();
+        if let Err(e) = walk.join() {
+            std::panic::resume_unwind(e);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::Result;
+use aoc_prelude::{BigInt, Itertools};
+use std::fmt::{Display, Formatter};
+use std::ops::{BitAnd, Sub};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Coords {
+    x: i64,
+    y: i64,
+    z: i64,
+}
+
+impl Coords {
+    fn cross(self, rhs: Coords) -> [i128; 3] {
+        let (a, b) = (self.widen(), rhs.widen());
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+
+    fn dot(self, rhs: Coords) -> i128 {
+        self.widen()
+            .iter()
+            .zip(rhs.widen())
+            .map(|(a, b)| a * b)
+            .sum()
+    }
+
+    fn widen(self) -> [i128; 3] {
+        [self.x, self.y, self.z].map(i128::from)
+    }
+}
+
+impl Sub<Coords> for Coords {
+    type Output = Coords;
+
+    fn sub(self, rhs: Coords) -> Self::Output {
+        Coords {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Hail {
+    orig: Coords,
+    v: Coords,
+}
+
+impl Display for Hail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Coords { x, y, z } = self.orig;
+        let Coords {
+            x: vx,
+            y: vy,
+            z: vz,
+        } = self.v;
+        f.write_str(&format!("{x}, {y}, {z} @ {vx}, {vy}, {vz}"))
+    }
+}
+
+impl<T: Iterator<Item = i64>> From<T> for Hail {
+    fn from(nums: T) -> Self {
+        let (x, y, z, vx, vy, vz) = nums.collect_tuple::<(_, _, _, _, _, _)>().unwrap();
+        Self {
+            orig: Coords { x, y, z },
+            v: Coords {
+                x: vx,
+                y: vy,
+                z: vz,
+            },
+        }
+    }
+}
+
+impl Hail {
+    fn slope(&self) -> f64 {
+        self.v.y as f64 / self.v.x as f64
+    }
+
+    fn y_intercept(&self) -> f64 {
+        self.orig.y as f64 - self.slope() * self.orig.x as f64
+    }
+
+    /// Whether something thrown from `rock` meets this hailstone at some time `t >= 0`.
+    fn is_hit_by(&self, rock: &Hail) -> bool {
+        // in the rock's frame, the hailstone has to fly straight at the origin
+        let rel = *self - rock.orig;
+        let v = rel.v - rock.v;
+        if v == (Coords { x: 0, y: 0, z: 0 }) {
+            return rel.orig == v;
+        }
+        rel.orig.cross(v) == [0; 3] && rel.orig.dot(v) <= 0
+    }
+}
+
+impl BitAnd<Hail> for Hail {
+    type Output = Option<(f64, f64)>;
+
+    fn bitand(self, rhs: Hail) -> Self::Output {
+        if self.v.x == rhs.v.x && self.v.y == rhs.v.y {
+            // assumption is they didn't give us the same trajectory twice
+            return None;
+        }
+        let x = (rhs.y_intercept() - self.y_intercept()) / (self.slope() - rhs.slope());
+        let y = self.slope() * x + self.y_intercept();
+        let ahead = |h: &Hail| {
+            (x - h.orig.x as f64).signum() == (h.v.x as f64).signum()
+                && (y - h.orig.y as f64).signum() == (h.v.y as f64).signum()
+        };
+        if ahead(&self) && ahead(&rhs) {
+            Some((x, self.slope() * x + self.y_intercept()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Sub<Coords> for Hail {
+    type Output = Hail;
+
+    fn sub(self, rhs: Coords) -> Self::Output {
+        Self {
+            orig: self.orig - rhs,
+            v: self.v,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hail>> {
+    input
+        .lines()
+        .map(|l| {
+            let nums = l
+                .replace('@', ",")
+                .split(',')
+                .map(|x| x.trim().parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()?;
+            if nums.len() != 6 {
+                return Err(format!("invalid hailstone: {l}").into());
+            }
+            Ok(nums.into_iter().into())
+        })
+        .collect()
+}
+
+/// Number of pairs whose paths cross inside the `t_lo..=t_hi` test area, ignoring z.
+fn crossings_within(hails: &[Hail], t_lo: f64, t_hi: f64) -> usize {
+    hails
+        .iter()
+        .tuple_combinations()
+        .filter_map(|(h1, h2)| h1.bitand(*h2))
+        .filter(|&(x, y)| t_lo <= x && x <= t_hi && t_lo <= y && y <= t_hi)
+        .count()
+}
+
+/// Determinant of a square matrix, by fraction-free Gaussian elimination (Bareiss), so every
+/// intermediate division is exact.
+fn determinant(mut m: Vec<Vec<BigInt>>) -> BigInt {
+    let zero = BigInt::from(0);
+    let n = m.len();
+    let mut prev = BigInt::from(1);
+    let mut sign = 1;
+    for k in 0..n {
+        if m[k][k] == zero {
+            let Some(pivot) = (k + 1..n).find(|&r| m[r][k] != zero) else {
+                return zero;
+            };
+            m.swap(k, pivot);
+            sign = -sign;
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                m[i][j] = (&m[i][j] * &m[k][k] - &m[i][k] * &m[k][j]) / &prev;
+            }
+        }
+        prev.clone_from(&m[k][k]);
+    }
+    prev * sign
+}
+
+/// Rows of `(P - p_a) x (V - v_a) = (P - p_b) x (V - v_b)`, which is linear in the rock's
+/// position `P` and velocity `V` once the `P x V` terms cancel out.
+/// Each row holds the coefficients of `[Px, Py, Pz, Vx, Vy, Vz]` followed by the constant.
+fn pair_equations(a: &Hail, b: &Hail) -> [[i128; 7]; 3] {
+    let ([dvx, dvy, dvz], [dpx, dpy, dpz]) = ((a.v - b.v).widen(), (a.orig - b.orig).widen());
+    let (ca, cb) = (a.orig.cross(a.v), b.orig.cross(b.v));
+    [
+        [0, dvz, -dvy, 0, -dpz, dpy, ca[0] - cb[0]],
+        [-dvz, 0, dvx, dpz, 0, -dpx, ca[1] - cb[1]],
+        [dvy, -dvx, 0, -dpy, dpx, 0, ca[2] - cb[2]],
+    ]
+}
+
+/// Solves for the rock's position and velocity from three hailstones, using Cramer's rule.
+/// `None` if the hailstones don't pin down a unique integer trajectory.
+fn rock_from(h0: &Hail, h1: &Hail, h2: &Hail) -> Option<Hail> {
+    let rows = [pair_equations(h0, h1), pair_equations(h0, h2)].concat();
+    let matrix = |replace: Option<usize>| {
+        rows.iter()
+            .map(|row| {
+                (0..6)
+                    .map(|col| BigInt::from(row[if Some(col) == replace { 6 } else { col }]))
+                    .collect()
+            })
+            .collect()
+    };
+
+    let det = determinant(matrix(None));
+    if det == BigInt::from(0) {
+        return None;
+    }
+    let mut unknowns = [0; 6];
+    for (col, u) in unknowns.iter_mut().enumerate() {
+        let num = determinant(matrix(Some(col)));
+        if &num % &det != BigInt::from(0) {
+            return None;
+        }
+        *u = i64::try_from(num / &det).ok()?;
+    }
+    Some(unknowns.into_iter().into())
+}
+
+/// Trajectory of a rock that hits every hailstone.
+fn throw_rock(hails: &[Hail]) -> Result<Hail> {
+    let rock = hails
+        .iter()
+        .tuple_combinations()
+        .find_map(|(h0, h1, h2)| rock_from(h0, h1, h2))
+        .ok_or("no three hailstones pin down the rock")?;
+    if let Some(missed) = hails.iter().find(|h| !h.is_hit_by(&rock)) {
+        return Err(format!("rock {rock} misses hailstone {missed}").into());
+    }
+    Ok(rock)
+}
+
+pub fn part1(hails: &[Hail]) -> Result<usize> {
+    Ok(crossings_within(
+        hails,
+        200_000_000_000_000_f64,
+        400_000_000_000_000_f64,
+    ))
+}
+
+pub fn part2(hails: &[Hail]) -> Result<i64> {
+    let rock = throw_rock(hails)?;
+    Ok(rock.orig.x + rock.orig.y + rock.orig.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_test_area() {
+        let hails = parse(include_str!("../../tests/examples/24.ex")).unwrap();
+        assert_eq!(crossings_within(&hails, 7.0, 27.0), 2);
+    }
+
+    #[test]
+    fn example_rock() {
+        let hails = parse(include_str!("../../tests/examples/24.ex")).unwrap();
+        assert_eq!(
+            throw_rock(&hails).unwrap().to_string(),
+            "24, 13, 10 @ -3, 1, 2"
+        );
+    }
+}
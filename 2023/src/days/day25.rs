@@ -0,0 +1,131 @@
+use crate::{export, Answer, Result};
+use aoc_prelude::HashMap;
+use std::collections::BinaryHeap;
+
+/// Components and the wires between them, by component index.
+pub struct Wiring<'a> {
+    names: Vec<&'a str>,
+    wires: Vec<(usize, usize)>,
+}
+
+pub fn parse(input: &str) -> Result<Wiring<'_>> {
+    let mut ids = HashMap::new();
+    let mut names = Vec::new();
+    let mut id = |name| {
+        *ids.entry(name).or_insert_with(|| {
+            names.push(name);
+            names.len() - 1
+        })
+    };
+
+    let mut wires = Vec::new();
+    for l in input.lines() {
+        let (from, rest) = l
+            .split_once(": ")
+            .ok_or_else(|| format!("invalid wiring: {l}"))?;
+        let from = id(from);
+        for to in rest.split_whitespace() {
+            wires.push((from, id(to)));
+        }
+    }
+
+    Ok(Wiring { names, wires })
+}
+
+/// Global minimum cut, by Stoer–Wagner.
+/// Returns the number of wires cut and the components on one side.
+fn min_cut(wiring: &Wiring) -> Option<(u32, Vec<usize>)> {
+    let n = wiring.names.len();
+    // merged vertices keep the summed weight of the wires to each neighbour
+    let mut adj = vec![HashMap::<usize, u32>::new(); n];
+    for &(a, b) in &wiring.wires {
+        *adj[a].entry(b).or_default() += 1;
+        *adj[b].entry(a).or_default() += 1;
+    }
+    let mut members = (0..n).map(|v| vec![v]).collect::<Vec<_>>();
+    let mut merged = vec![false; n];
+
+    let mut best: Option<(u32, Vec<usize>)> = None;
+    for phase in 0..n.saturating_sub(1) {
+        // maximum adjacency ordering, the last two vertices get merged
+        let mut added = merged.clone();
+        let mut weight = vec![0; n];
+        let mut heap = BinaryHeap::from([(0, merged.iter().position(|&m| !m)?)]);
+        let (mut prev, mut last) = (usize::MAX, usize::MAX);
+        for _ in 0..n - phase {
+            let v = loop {
+                match heap.pop() {
+                    Some((w, v)) if !added[v] && w == weight[v] => break v,
+                    Some(_) => {}
+                    // the rest isn't connected to what we have so far
+                    None => break added.iter().position(|&a| !a)?,
+                }
+            };
+            added[v] = true;
+            (prev, last) = (last, v);
+            for (&u, &w) in &adj[v] {
+                if !added[u] {
+                    weight[u] += w;
+                    heap.push((weight[u], u));
+                }
+            }
+        }
+
+        // the cut of the phase separates `last` from everything else
+        if best.as_ref().is_none_or(|(cut, _)| weight[last] < *cut) {
+            best = Some((weight[last], members[last].clone()));
+        }
+
+        for (u, w) in std::mem::take(&mut adj[last]) {
+            adj[u].remove(&last);
+            if u != prev {
+                *adj[prev].entry(u).or_default() += w;
+                *adj[u].entry(prev).or_default() += w;
+            }
+        }
+        let moved = std::mem::take(&mut members[last]);
+        members[prev].extend(moved);
+        merged[last] = true;
+    }
+    best
+}
+
+pub fn part1(wiring: &Wiring) -> Result<usize> {
+    let (_, side) = min_cut(wiring).ok_or("need at least two components")?;
+    Ok(side.len() * (wiring.names.len() - side.len()))
+}
+
+/// Day 25 only has one puzzle.
+pub fn part2(_wiring: &Wiring) -> Result<Answer> {
+    Ok(Answer::Unsolved)
+}
+
+/// The wiring, with the wires of the minimum cut highlighted.
+pub fn graph(wiring: &Wiring) -> Result<export::Graph> {
+    let (_, side) = min_cut(wiring).ok_or("need at least two components")?;
+    let mut in_side = vec![false; wiring.names.len()];
+    for v in side {
+        in_side[v] = true;
+    }
+
+    let mut graph = export::Graph::undirected();
+    for &(a, b) in &wiring.wires {
+        let (a_name, b_name) = (wiring.names[a], wiring.names[b]);
+        graph.edge(a_name, b_name, None);
+        if in_side[a] != in_side[b] {
+            graph.highlight_edge(a_name, b_name);
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_cuts_three_wires() {
+        let wiring = parse(include_str!("../../tests/examples/25.ex")).unwrap();
+        assert_eq!(min_cut(&wiring).map(|(cut, _)| cut), Some(3));
+    }
+}
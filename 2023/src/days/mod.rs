@@ -0,0 +1,153 @@
+//! One module per puzzle, each exposing `parse(&str) -> Result<Input>` and
+//! `part1(&Input)` / `part2(&Input)` returning anything that converts into an [`Answer`](crate::Answer).
+//!
+//! Work that both parts need, like day 21's BFS distances or day 22's support graph,
+//! belongs in the parsed input so it's only done once.
+// solvers error out on malformed input and only panic on broken invariants
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+
+use crate::runner::Run;
+use crate::solver;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub type SolveFn = fn(&str) -> Run;
+
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub num: u8,
+    pub solve: SolveFn,
+}
+
+/// Every solved day, in order.
+pub const DAYS: [Day; 25] = [
+    Day {
+        num: 1,
+        solve: solver!(day01),
+    },
+    Day {
+        num: 2,
+        solve: solver!(day02),
+    },
+    Day {
+        num: 3,
+        solve: solver!(day03),
+    },
+    Day {
+        num: 4,
+        solve: solver!(day04),
+    },
+    Day {
+        num: 5,
+        solve: solver!(day05),
+    },
+    Day {
+        num: 6,
+        solve: solver!(day06),
+    },
+    Day {
+        num: 7,
+        solve: solver!(day07),
+    },
+    Day {
+        num: 8,
+        solve: solver!(day08),
+    },
+    Day {
+        num: 9,
+        solve: solver!(day09),
+    },
+    Day {
+        num: 10,
+        solve: solver!(day10),
+    },
+    Day {
+        num: 11,
+        solve: solver!(day11),
+    },
+    Day {
+        num: 12,
+        solve: solver!(day12),
+    },
+    Day {
+        num: 13,
+        solve: solver!(day13),
+    },
+    Day {
+        num: 14,
+        solve: solver!(day14),
+    },
+    Day {
+        num: 15,
+        solve: solver!(day15),
+    },
+    Day {
+        num: 16,
+        solve: solver!(day16),
+    },
+    Day {
+        num: 17,
+        solve: solver!(day17),
+    },
+    Day {
+        num: 18,
+        solve: solver!(day18),
+    },
+    Day {
+        num: 19,
+        solve: solver!(day19),
+    },
+    Day {
+        num: 20,
+        solve: solver!(day20),
+    },
+    Day {
+        num: 21,
+        solve: solver!(day21),
+    },
+    Day {
+        num: 22,
+        solve: solver!(day22),
+    },
+    Day {
+        num: 23,
+        solve: solver!(day23),
+    },
+    Day {
+        num: 24,
+        solve: solver!(day24),
+    },
+    Day {
+        num: 25,
+        solve: solver!(day25),
+    },
+];
+
+#[must_use]
+pub fn get(num: u8) -> Option<Day> {
+    DAYS.iter().find(|d| d.num == num).copied()
+}
@@ -0,0 +1,297 @@
+use crate::Result;
+use aoc_prelude::HashMap;
+use std::fmt::{Display, Write};
+use std::path::Path;
+
+/// Output format of a graph export, picked from the file extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    GraphMl,
+}
+
+impl Format {
+    /// `.dot` and `.gv` for Graphviz, `.graphml` for `GraphML`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on any other extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("dot" | "gv") => Ok(Self::Dot),
+            Some("graphml") => Ok(Self::GraphMl),
+            _ => Err(format!(
+                "cannot tell the graph format of {}, use .dot, .gv or .graphml",
+                path.display()
+            )
+            .into()),
+        }
+    }
+}
+
+struct Node {
+    id: String,
+    label: String,
+    highlight: bool,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    highlight: bool,
+}
+
+/// A puzzle graph on its way to a file, with optional highlights for the interesting bits.
+pub struct Graph {
+    directed: bool,
+    nodes: Vec<Node>,
+    ids: HashMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    #[must_use]
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    #[must_use]
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Self {
+            directed,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a node, or relabels it if it's already there.
+    pub fn node(&mut self, id: impl Display, label: impl Display) -> &mut Self {
+        let idx = self.index(id);
+        self.nodes[idx].label = label.to_string();
+        self
+    }
+
+    /// Adds an edge, and its endpoints if they're missing.
+    pub fn edge(
+        &mut self,
+        from: impl Display,
+        to: impl Display,
+        label: Option<String>,
+    ) -> &mut Self {
+        let (from, to) = (self.index(from), self.index(to));
+        self.edges.push(Edge {
+            from,
+            to,
+            label,
+            highlight: false,
+        });
+        self
+    }
+
+    pub fn highlight_node(&mut self, id: impl Display) -> &mut Self {
+        let idx = self.index(id);
+        self.nodes[idx].highlight = true;
+        self
+    }
+
+    /// Highlights every edge between `from` and `to`, either way round if undirected.
+    pub fn highlight_edge(&mut self, from: impl Display, to: impl Display) -> &mut Self {
+        let (from, to) = (self.index(from), self.index(to));
+        let directed = self.directed;
+        for e in &mut self.edges {
+            if (e.from, e.to) == (from, to) || (!directed && (e.to, e.from) == (from, to)) {
+                e.highlight = true;
+            }
+        }
+        self
+    }
+
+    fn index(&mut self, id: impl Display) -> usize {
+        let id = id.to_string();
+        if let Some(&idx) = self.ids.get(&id) {
+            return idx;
+        }
+        self.nodes.push(Node {
+            label: id.clone(),
+            id: id.clone(),
+            highlight: false,
+        });
+        self.ids.insert(id, self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+
+    /// Graphviz rendering, highlights in red.
+    #[must_use]
+    pub fn dot(&self) -> String {
+        let (kind, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let highlight = |on| if on { ", color=red, penwidth=2" } else { "" };
+
+        let mut out = format!("{kind} G {{\n");
+        for n in &self.nodes {
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\"{}];",
+                dot_escape(&n.id),
+                dot_escape(&n.label),
+                highlight(n.highlight)
+            );
+        }
+        for e in &self.edges {
+            let label = e.label.as_deref().map(dot_escape).unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "  \"{}\" {arrow} \"{}\" [label=\"{label}\"{}];",
+                dot_escape(&self.nodes[e.from].id),
+                dot_escape(&self.nodes[e.to].id),
+                highlight(e.highlight)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// `GraphML` rendering, with `label` and `highlight` attributes on nodes and edges.
+    #[must_use]
+    pub fn graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"all\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"highlight\" for=\"all\" attr.name=\"highlight\" attr.type=\"boolean\">\n",
+            "    <default>false</default>\n",
+            "  </key>\n",
+        ));
+        let edge_default = if self.directed {
+            "directed"
+        } else {
+            "undirected"
+        };
+        let _ = writeln!(out, "  <graph id=\"G\" edgedefault=\"{edge_default}\">");
+        let highlight = |on| {
+            if on {
+                "<data key=\"highlight\">true</data>"
+            } else {
+                ""
+            }
+        };
+
+        for (i, n) in self.nodes.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <node id=\"n{i}\"><data key=\"label\">{}</data>{}</node>",
+                xml_escape(&n.label),
+                highlight(n.highlight)
+            );
+        }
+        for e in &self.edges {
+            let label = e.label.as_deref().map_or(String::new(), |x| {
+                format!("<data key=\"label\">{}</data>", xml_escape(x))
+            });
+            let _ = writeln!(
+                out,
+                "    <edge source=\"n{}\" target=\"n{}\">{label}{}</edge>",
+                e.from,
+                e.to,
+                highlight(e.highlight)
+            );
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// Writes the graph in the format matching the extension of `path`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on an unknown extension or if the file can't be written.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let data = match Format::from_path(path)? {
+            Format::Dot => self.dot(),
+            Format::GraphMl => self.graphml(),
+        };
+        std::fs::write(path, data).map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Graph {
+        let mut g = Graph::directed();
+        g.node("a", "start \"a\"")
+            .edge("a", "b", Some("L".to_owned()))
+            .edge("b", "a", None)
+            .highlight_edge("a", "b")
+            .highlight_node("b");
+        g
+    }
+
+    #[test]
+    fn dot() {
+        assert_eq!(
+            sample().dot(),
+            concat!(
+                "digraph G {\n",
+                "  \"a\" [label=\"start \\\"a\\\"\"];\n",
+                "  \"b\" [label=\"b\", color=red, penwidth=2];\n",
+                "  \"a\" -> \"b\" [label=\"L\", color=red, penwidth=2];\n",
+                "  \"b\" -> \"a\" [label=\"\"];\n",
+                "}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn graphml() {
+        let xml = sample().graphml();
+        assert!(xml.contains("edgedefault=\"directed\""));
+        assert!(
+            xml.contains("<node id=\"n0\"><data key=\"label\">start &quot;a&quot;</data></node>")
+        );
+        assert!(xml.contains(
+            "<edge source=\"n0\" target=\"n1\"><data key=\"label\">L</data><data key=\"highlight\">true</data></edge>"
+        ));
+        assert!(xml.contains("<edge source=\"n1\" target=\"n0\"></edge>"));
+    }
+
+    #[test]
+    fn undirected_highlight_ignores_direction() {
+        let mut g = Graph::undirected();
+        g.edge("a", "b", None).highlight_edge("b", "a");
+        assert!(g
+            .dot()
+            .contains("\"a\" -- \"b\" [label=\"\", color=red, penwidth=2];"));
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("x.gv")).unwrap(), Format::Dot);
+        assert_eq!(
+            Format::from_path(Path::new("out/x.graphml")).unwrap(),
+            Format::GraphMl
+        );
+        assert!(Format::from_path(Path::new("x.svg")).is_err());
+    }
+}
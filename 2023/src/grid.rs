@@ -0,0 +1,362 @@
+use crate::{ConstMap, Result, Symmetric};
+use aoc_2dmap::prelude::{Map, Pos};
+use aoc_prelude::Itertools;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Why a map failed to parse. Rows and columns are zero-based.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseMapError {
+    /// The map doesn't have the size the caller asked for, as `(width, height)`.
+    Dimensions {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    /// A row isn't as wide as the first one.
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    InvalidChar {
+        row: usize,
+        col: usize,
+        c: char,
+    },
+}
+
+impl Display for ParseMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dimensions {
+                expected: (ew, eh),
+                actual: (aw, ah),
+            } => write!(f, "expected a {ew}x{eh} map, got {aw}x{ah}"),
+            Self::RaggedRow {
+                row,
+                expected,
+                actual,
+            } => write!(f, "row {row} is {actual} wide instead of {expected}"),
+            Self::InvalidChar { row, col, c } => {
+                write!(f, "invalid character {c:?} at row {row}, column {col}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMapError {}
+
+/// Heap-backed rectangular grid, stored row by row.
+///
+/// The runtime-sized sibling of [`ConstMap`], for when the size isn't known up front.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Grid<T = char> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// # Errors
+    ///
+    /// Fails if `cells` doesn't hold exactly `width * height` elements.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            return Err(format!(
+                "expected {width}x{height} = {} cells, got {}",
+                width * height,
+                cells.len()
+            )
+            .into());
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn idx(&self, p: Pos) -> Option<usize> {
+        let (x, y) = (usize::try_from(p.x).ok()?, usize::try_from(p.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    #[must_use]
+    pub fn get_ref(&self, p: Pos) -> Option<&T> {
+        self.idx(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        self.idx(p).map(|i| &mut self.cells[i])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_exact_mut(self.width.max(1))
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `val`.
+    #[must_use]
+    pub fn filled(width: usize, height: usize, val: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![val; width * height],
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, p: Pos) -> Option<T> {
+        self.get_ref(p).copied()
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = T> + '_ {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .copied()
+    }
+}
+
+impl<T: Copy> Symmetric for Grid<T> {
+    fn transpose(&mut self) {
+        let cells = (0..self.width).flat_map(|x| self.col(x)).collect();
+        *self = Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        };
+    }
+
+    fn flip_vertical(&mut self) {
+        for row in self.rows_mut() {
+            row.reverse();
+        }
+    }
+
+    fn flip_horizontal(&mut self) {
+        let width = self.width;
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.cells.split_at_mut((self.height - 1 - y) * width);
+            top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
+        }
+    }
+
+    fn rotate_180(&mut self) {
+        self.cells.reverse();
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.rows().map(|x| x.iter().collect::<String>()).join("\n"))
+    }
+}
+
+/// Reads one cell per character, one row per line. Trailing newlines are ignored.
+fn parse_cells<T>(s: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, ParseMapError> {
+    let lines = s.trim_end_matches(['\n', '\r']).lines().collect::<Vec<_>>();
+    let width = lines.first().map_or(0, |x| x.chars().count());
+
+    let mut cells = Vec::with_capacity(width * lines.len());
+    for (row, line) in lines.iter().enumerate() {
+        let actual = line.chars().count();
+        if actual != width {
+            return Err(ParseMapError::RaggedRow {
+                row,
+                expected: width,
+                actual,
+            });
+        }
+        for (col, c) in line.chars().enumerate() {
+            cells.push(cell(c).ok_or(ParseMapError::InvalidChar { row, col, c })?);
+        }
+    }
+    Ok(Grid {
+        width,
+        height: lines.len(),
+        cells,
+    })
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cells(s, Some)
+    }
+}
+
+impl FromStr for Grid<u32> {
+    type Err = ParseMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cells(s, |c| c.to_digit(10))
+    }
+}
+
+/// For the days still working on an `aoc_2dmap` map: parsing through [`Grid`] checks the rows
+/// line up first, where [`Map::new`] would panic.
+impl<T> From<Grid<T>> for Map<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Map::new((grid.width, grid.height), grid.cells)
+    }
+}
+
+impl<const M: usize, T: Copy> From<ConstMap<M, T>> for Grid<T> {
+    fn from(map: ConstMap<M, T>) -> Self {
+        Self {
+            width: M,
+            height: M,
+            cells: map.inner.into_iter().flatten().collect(),
+        }
+    }
+}
+
+impl<const M: usize, T: Copy + Default> TryFrom<&Grid<T>> for ConstMap<M, T> {
+    type Error = ParseMapError;
+
+    /// Moves a grid onto the const-generic fast path, if it has the right size.
+    fn try_from(grid: &Grid<T>) -> Result<Self, Self::Error> {
+        if grid.width != M || grid.height != M {
+            return Err(ParseMapError::Dimensions {
+                expected: (M, M),
+                actual: (grid.width, grid.height),
+            });
+        }
+        let mut inner = [[T::default(); M]; M];
+        for (row, cells) in inner.iter_mut().zip(grid.rows()) {
+            row.copy_from_slice(cells);
+        }
+        Ok(ConstMap { inner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn rotations() {
+        let mut g = grid("abc\ndef\n");
+        g.rotate_cw();
+        assert_eq!(g, grid("da\neb\nfc"));
+        g.rotate_cw();
+        assert_eq!(g, grid("fed\ncba"));
+        g.rotate_ccw();
+        assert_eq!(g, grid("da\neb\nfc"));
+        g.rotate_180();
+        assert_eq!(g, grid("cf\nbe\nad"));
+    }
+
+    #[test]
+    fn transpose_and_flips() {
+        let mut g = grid("abc\ndef");
+        g.transpose();
+        assert_eq!(g.to_string(), "ad\nbe\ncf");
+        g.flip_vertical();
+        assert_eq!(g.to_string(), "da\neb\nfc");
+        g.flip_horizontal();
+        assert_eq!(g.to_string(), "fc\neb\nda");
+    }
+
+    #[test]
+    fn get_out_of_bounds() {
+        let g = grid("ab\ncd\nef");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g.get(Pos::new(1, 2)), Some('f'));
+        assert_eq!(g.get(Pos::new(2, 0)), None);
+        assert_eq!(g.get(Pos::new(0, -1)), None);
+    }
+
+    #[test]
+    fn const_map_round_trip() {
+        let g = grid("ab\ncd");
+        let m = ConstMap::<2>::try_from(&g).unwrap();
+        assert_eq!(Grid::from(m), g);
+        assert!(ConstMap::<3>::try_from(&g).is_err());
+    }
+
+    #[test]
+    fn days_on_maps_reject_ragged_rows() {
+        use crate::days::{day03, day10, day16, day23};
+        let ragged = "#.#\n#.\n#.#\n";
+        let expected = "row 1 is 2 wide instead of 3";
+        assert_eq!(day03::parse(ragged).err().unwrap().to_string(), expected);
+        assert_eq!(day10::parse(ragged).err().unwrap().to_string(), expected);
+        assert_eq!(day23::parse(ragged).err().unwrap().to_string(), expected);
+        let ragged = "...\n..\n...\n";
+        assert_eq!(day16::parse(ragged).err().unwrap().to_string(), expected);
+        assert!(day23::parse("").is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "ab\nc".parse::<Grid>(),
+            Err(ParseMapError::RaggedRow {
+                row: 1,
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            "12\n3x".parse::<Grid<u32>>(),
+            Err(ParseMapError::InvalidChar {
+                row: 1,
+                col: 1,
+                c: 'x'
+            })
+        );
+        assert_eq!(
+            "ab\ncd\nef\n".parse::<ConstMap<2>>(),
+            Err(ParseMapError::Dimensions {
+                expected: (2, 2),
+                actual: (2, 3)
+            })
+        );
+        assert_eq!(
+            "a".parse::<ConstMap<2>>(),
+            Err(ParseMapError::Dimensions {
+                expected: (2, 2),
+                actual: (1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn const_map_from_str() {
+        let m = "12\n34\n".parse::<ConstMap<2, u32>>().unwrap();
+        assert_eq!(m.inner, [[1, 2], [3, 4]]);
+        assert_eq!(
+            "ab\ncd".parse::<ConstMap<2>>().unwrap().to_string(),
+            "ab\ncd"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::cli::flag_value;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Environment variable that points a day at a different input file.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+const INPUT_FLAG: &str = "--input";
+const STDIN_PATH: &str = "-";
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Precedence: `--input <path>` flag, then `$AOC_INPUT`, then `inputs/NN.in`.
+    /// A path of `-` reads from stdin.
+    pub fn resolve<I: IntoIterator<Item = String>>(day: u8, args: I) -> Self {
+        let path = flag_value(args, INPUT_FLAG)
+            .or_else(|| env::var(INPUT_ENV).ok().filter(|x| !x.is_empty()))
+            .map_or_else(|| default_path(day), PathBuf::from);
+
+        if path == Path::new(STDIN_PATH) {
+            Self::Stdin
+        } else {
+            Self::File(path)
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file or stdin cannot be read as UTF-8 text.
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut buf = String::new();
+                io::stdin().read_to_string(&mut buf)?;
+                Ok(buf)
+            }
+            Source::File(path) => fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+        }
+    }
+}
+
+/// The checked-in location of a day's input: `2023/inputs/NN.in`.
+#[must_use]
+pub fn default_path(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("inputs")
+        .join(format!("{day:0>2}.in"))
+}
+
+/// Extracts the day number out of a binary name such as `day07`.
+#[must_use]
+pub fn day_from_bin(bin_name: &str) -> Option<u8> {
+    bin_name.strip_prefix("day")?.parse().ok()
+}
+
+/// Loads the input for the binary called `bin_name`, honoring the CLI and env overrides.
+///
+/// # Errors
+///
+/// Fails if the day can't be inferred from `bin_name` or if the input can't be read.
+pub fn load(bin_name: &str) -> io::Result<String> {
+    let day = day_from_bin(bin_name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot infer day from binary name: {bin_name}"),
+        )
+    })?;
+    Source::resolve(day, env::args().skip(1)).read()
+}
@@ -0,0 +1,453 @@
+use aoc_prelude::PrimInt;
+use std::fmt::{self, Debug, Formatter};
+
+/// The integers `start..end`, empty unless `start < end`.
+///
+/// Half-open, so splitting at `x` gives `start..x` and `x..end` with nothing to add or subtract.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    #[must_use]
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    #[must_use]
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    #[must_use]
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    fn non_empty(self) -> Option<Self> {
+        (!self.is_empty()).then_some(self)
+    }
+
+    /// The integers in both, unless there are none.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        Self::new(self.start.max(other.start), self.end.min(other.end)).non_empty()
+    }
+
+    /// The integers below `at`, and those from `at` on, leaving out whichever side is empty.
+    #[must_use]
+    pub fn split_at(self, at: T) -> (Option<Self>, Option<Self>) {
+        (
+            Self::new(self.start, self.end.min(at)).non_empty(),
+            Self::new(self.start.max(at), self.end).non_empty(),
+        )
+    }
+
+    /// The integers in `self` but not in `other`: at most two pieces, the lower one first.
+    pub fn difference(self, other: Self) -> impl Iterator<Item = Self> {
+        let (below, above) = if other.is_empty() {
+            (self.non_empty(), None)
+        } else {
+            (self.split_at(other.start).0, self.split_at(other.end).1)
+        };
+        below.into_iter().chain(above)
+    }
+
+    /// The same integers as all of `intervals` together, as sorted disjoint intervals that
+    /// don't touch either, i.e. as few as there can be.
+    #[must_use]
+    pub fn normalize(intervals: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut intervals = intervals
+            .into_iter()
+            .filter(|i| !i.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_unstable();
+
+        let mut merged = Vec::<Self>::with_capacity(intervals.len());
+        for i in intervals {
+            match merged.last_mut() {
+                Some(last) if i.start <= last.end => last.end = last.end.max(i.end),
+                _ => merged.push(i),
+            }
+        }
+        merged
+    }
+}
+
+impl<T: Debug> Debug for Interval<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+/// A box of integer points in `N` dimensions, one interval per axis: empty if any of them is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HyperRect<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: PrimInt, const N: usize> HyperRect<T, N> {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    /// How many points it holds.
+    ///
+    /// # Panics
+    /// If an axis is longer than `u128` goes.
+    #[must_use]
+    pub fn volume(&self) -> u128 {
+        self.0
+            .iter()
+            .map(|i| i.len().to_u128().expect("axis length fits in u128"))
+            .product()
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.0.iter().zip(point).all(|(i, &x)| i.contains(x))
+    }
+
+    /// The points in both, unless there are none.
+    #[must_use]
+    pub fn intersection(mut self, other: &Self) -> Option<Self> {
+        for (i, o) in self.0.iter_mut().zip(&other.0) {
+            *i = i.intersection(*o)?;
+        }
+        Some(self)
+    }
+
+    /// The points below `at` along `axis`, and those from `at` on, see [`Interval::split_at`].
+    ///
+    /// # Panics
+    /// If `axis` isn't below `N`.
+    #[must_use]
+    pub fn split(self, axis: usize, at: T) -> (Option<Self>, Option<Self>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+        let (below, above) = self.0[axis].split_at(at);
+        let with = |i: Interval<T>| {
+            let mut rect = self;
+            rect.0[axis] = i;
+            rect
+        };
+        (below.map(with), above.map(with))
+    }
+
+    /// The points in `self` but not in `other`, as at most `2 * N` disjoint boxes.
+    ///
+    /// Axis by axis, the slabs on either side of `other` are cut off and what's left narrows
+    /// down to the overlap, which is dropped in the end.
+    #[must_use]
+    pub fn difference(self, other: &Self) -> Vec<Self> {
+        if self.intersection(other).is_none() {
+            return if self.is_empty() { vec![] } else { vec![self] };
+        }
+        let mut pieces = Vec::new();
+        let mut rest = self;
+        for axis in 0..N {
+            let (below, from) = rest.split(axis, other.0[axis].start);
+            let (mid, above) = from.map_or((None, None), |r| r.split(axis, other.0[axis].end));
+            pieces.extend(below.into_iter().chain(above));
+            match mid {
+                Some(mid) => rest = mid,
+                None => break,
+            }
+        }
+        pieces
+    }
+
+    /// The same points as all of `rects` together, as disjoint boxes: each one keeps only what
+    /// the ones before it don't cover.
+    #[must_use]
+    pub fn disjoint_union(rects: impl IntoIterator<Item = Self>) -> Vec<Self> {
+        let mut union: Vec<Self> = Vec::new();
+        for rect in rects {
+            let mut pieces = vec![rect];
+            for done in &union {
+                pieces = pieces
+                    .into_iter()
+                    .flat_map(|p| p.difference(done))
+                    .collect();
+            }
+            union.extend(pieces.into_iter().filter(|p| !p.is_empty()));
+        }
+        union
+    }
+}
+
+/// Moves a few intervals to start elsewhere, leaving every other integer where it is. Where the
+/// intervals overlap, the first one added wins.
+#[derive(Clone, Debug, Default)]
+pub struct Piecewise<T> {
+    /// Each interval and where it starts after the move.
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T: PrimInt> Piecewise<T> {
+    /// Moves `from` to start at `to`, unless an earlier piece already covers it.
+    pub fn push(&mut self, from: Interval<T>, to: T) {
+        self.pieces.push((from, to));
+    }
+
+    #[must_use]
+    pub fn apply(&self, x: T) -> T {
+        self.pieces
+            .iter()
+            .find(|(from, _)| from.contains(x))
+            .map_or(x, |&(from, to)| to + (x - from.start))
+    }
+
+    /// Where the integers of `interval` end up: each bit of it in exactly one piece of the result,
+    /// in no particular order.
+    #[must_use]
+    pub fn image(&self, interval: Interval<T>) -> Vec<Interval<T>> {
+        let mut rest = interval.non_empty().into_iter().collect::<Vec<_>>();
+        let mut moved = Vec::new();
+        for &(from, to) in &self.pieces {
+            let mut left = Vec::with_capacity(rest.len());
+            for r in rest {
+                if let Some(hit) = r.intersection(from) {
+                    let shift = |x: T| to + (x - from.start);
+                    moved.push(Interval::new(shift(hit.start), shift(hit.end)));
+                    left.extend(r.difference(from));
+                } else {
+                    left.push(r);
+                }
+            }
+            rest = left;
+        }
+        moved.extend(rest);
+        moved
+    }
+}
+
+impl<T> FromIterator<(Interval<T>, T)> for Piecewise<T> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, T)>>(iter: I) -> Self {
+        Self {
+            pieces: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn iv(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end)
+    }
+
+    fn points(intervals: &[Interval<i32>]) -> Vec<i32> {
+        let mut points = intervals
+            .iter()
+            .flat_map(|i| i.start..i.end)
+            .collect::<Vec<_>>();
+        points.sort_unstable();
+        points
+    }
+
+    fn rect(bounds: [(i32, i32); 2]) -> HyperRect<i32, 2> {
+        HyperRect(bounds.map(|(s, e)| iv(s, e)))
+    }
+
+    fn rect_points(rects: &[HyperRect<i32, 2>]) -> Vec<[i32; 2]> {
+        let mut points = rects
+            .iter()
+            .flat_map(|r| {
+                let [x, y] = r.0;
+                (x.start..x.end).flat_map(move |px| (y.start..y.end).map(move |py| [px, py]))
+            })
+            .collect::<Vec<_>>();
+        points.sort_unstable();
+        points
+    }
+
+    #[test]
+    fn bounds() {
+        let i = iv(3, 7);
+        assert_eq!(i.len(), 4);
+        assert!(i.contains(3) && i.contains(6));
+        assert!(!i.contains(2) && !i.contains(7));
+        assert!(iv(5, 5).is_empty());
+        assert_eq!(iv(7, 3).len(), 0);
+        assert_eq!(format!("{i:?}"), "3..7");
+    }
+
+    #[test]
+    fn intersections() {
+        assert_eq!(iv(0, 10).intersection(iv(5, 15)), Some(iv(5, 10)));
+        assert_eq!(iv(0, 10).intersection(iv(2, 3)), Some(iv(2, 3)));
+        // touching isn't overlapping
+        assert_eq!(iv(0, 10).intersection(iv(10, 20)), None);
+        assert_eq!(iv(0, 10).intersection(iv(4, 4)), None);
+    }
+
+    #[test]
+    fn splits() {
+        assert_eq!(iv(0, 10).split_at(4), (Some(iv(0, 4)), Some(iv(4, 10))));
+        assert_eq!(iv(0, 10).split_at(0), (None, Some(iv(0, 10))));
+        assert_eq!(iv(0, 10).split_at(10), (Some(iv(0, 10)), None));
+        assert_eq!(iv(0, 10).split_at(-5), (None, Some(iv(0, 10))));
+        assert_eq!(iv(4, 4).split_at(4), (None, None));
+    }
+
+    #[test]
+    fn differences() {
+        let diff = |a: Interval<i32>, b| a.difference(b).collect::<Vec<_>>();
+        assert_eq!(diff(iv(0, 10), iv(3, 5)), [iv(0, 3), iv(5, 10)]);
+        assert_eq!(diff(iv(0, 10), iv(0, 5)), [iv(5, 10)]);
+        assert_eq!(diff(iv(0, 10), iv(5, 10)), [iv(0, 5)]);
+        assert_eq!(diff(iv(0, 10), iv(-5, 15)), []);
+        assert_eq!(diff(iv(0, 10), iv(10, 15)), [iv(0, 10)]);
+        assert_eq!(diff(iv(0, 10), iv(5, 5)), [iv(0, 10)]);
+    }
+
+    #[test]
+    fn normalizes() {
+        let merged = Interval::normalize([iv(8, 9), iv(0, 3), iv(3, 5), iv(2, 4), iv(6, 6)]);
+        assert_eq!(merged, [iv(0, 5), iv(8, 9)]);
+        assert_eq!(
+            Interval::normalize([iv(1, 2), iv(3, 4)]),
+            [iv(1, 2), iv(3, 4)]
+        );
+        assert_eq!(Interval::<i32>::normalize([]), []);
+    }
+
+    #[test]
+    fn moves_pieces() {
+        let map = [(iv(98, 100), 50), (iv(50, 98), 52)]
+            .into_iter()
+            .collect::<Piecewise<_>>();
+        assert_eq!(map.apply(49), 49);
+        assert_eq!(map.apply(50), 52);
+        assert_eq!(map.apply(97), 99);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(100), 100);
+
+        let mut image = map.image(iv(40, 110));
+        image.sort_unstable();
+        assert_eq!(image, [iv(40, 50), iv(50, 52), iv(52, 100), iv(100, 110)]);
+        assert_eq!(Interval::normalize(image), [iv(40, 110)]);
+    }
+
+    #[test]
+    fn first_piece_wins() {
+        let mut map = Piecewise::default();
+        map.push(iv(0, 10), 100);
+        map.push(iv(5, 15), 200);
+        assert_eq!(map.apply(7), 107);
+        assert_eq!(map.apply(12), 207);
+        let mut image = map.image(iv(0, 20));
+        image.sort_unstable();
+        assert_eq!(image, [iv(15, 20), iv(100, 110), iv(205, 210)]);
+    }
+
+    #[test]
+    fn boxes() {
+        let r = rect([(0, 4), (0, 3)]);
+        assert_eq!(r.volume(), 12);
+        assert!(r.contains(&[3, 2]) && !r.contains(&[4, 2]));
+        assert_eq!(
+            r.intersection(&rect([(2, 9), (1, 2)])),
+            Some(rect([(2, 4), (1, 2)]))
+        );
+        assert_eq!(r.intersection(&rect([(4, 9), (0, 3)])), None);
+        assert_eq!(
+            r.split(1, 1),
+            (Some(rect([(0, 4), (0, 1)])), Some(rect([(0, 4), (1, 3)])))
+        );
+        assert_eq!(rect([(0, 0), (0, 3)]).volume(), 0);
+        assert!(rect([(0, 4), (2, 2)]).is_empty());
+    }
+
+    #[test]
+    fn box_differences() {
+        let r = rect([(0, 4), (0, 4)]);
+        // a hole in the middle leaves four slabs around it
+        let ring = r.difference(&rect([(1, 3), (1, 3)]));
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.iter().map(HyperRect::volume).sum::<u128>(), 12);
+        assert_eq!(r.difference(&r), []);
+        assert_eq!(r.difference(&rect([(4, 8), (0, 4)])), [r]);
+        assert_eq!(
+            r.difference(&rect([(2, 8), (-1, 8)])),
+            [rect([(0, 2), (0, 4)])]
+        );
+    }
+
+    fn intervals() -> impl Strategy<Value = Interval<i32>> {
+        (-20..20, -20..20).prop_map(|(s, e)| iv(s, e))
+    }
+
+    fn rects() -> impl Strategy<Value = HyperRect<i32, 2>> {
+        (intervals(), intervals()).prop_map(|(x, y)| HyperRect([x, y]))
+    }
+
+    proptest! {
+        #[test]
+        fn interval_ops_match_points(a in intervals(), b in intervals()) {
+            let (pa, pb) = (points(&[a]), points(&[b]));
+            let both = pa.iter().copied().filter(|x| pb.contains(x)).collect::<Vec<_>>();
+            let only_a = pa.iter().copied().filter(|x| !pb.contains(x)).collect::<Vec<_>>();
+            prop_assert_eq!(points(&a.intersection(b).into_iter().collect::<Vec<_>>()), both);
+            prop_assert_eq!(points(&a.difference(b).collect::<Vec<_>>()), only_a);
+        }
+
+        #[test]
+        fn normalize_keeps_points(is in prop::collection::vec(intervals(), 0..8)) {
+            let merged = Interval::normalize(is.iter().copied());
+            let mut expected = points(&is);
+            expected.dedup();
+            prop_assert_eq!(points(&merged), expected);
+            for w in merged.windows(2) {
+                prop_assert!(w[0].end < w[1].start, "{:?}", merged);
+            }
+        }
+
+        #[test]
+        fn image_matches_apply(
+            pieces in prop::collection::vec((intervals(), -50..50), 0..5),
+            i in intervals(),
+        ) {
+            let map = pieces.into_iter().collect::<Piecewise<_>>();
+            let mut expected = points(&[i]).into_iter().map(|x| map.apply(x)).collect::<Vec<_>>();
+            expected.sort_unstable();
+            prop_assert_eq!(points(&map.image(i)), expected);
+        }
+
+        #[test]
+        fn box_ops_match_points(a in rects(), b in rects()) {
+            let (pa, pb) = (rect_points(&[a]), rect_points(&[b]));
+            let only_a = pa.iter().copied().filter(|p| !pb.contains(p)).collect::<Vec<_>>();
+            let diff = a.difference(&b);
+            prop_assert!(diff.len() <= 4);
+            // disjoint, so no point shows up twice
+            prop_assert_eq!(rect_points(&diff), only_a);
+            prop_assert_eq!(
+                a.intersection(&b).map_or(0, |r| r.volume()),
+                pa.iter().filter(|p| pb.contains(p)).count() as u128
+            );
+        }
+
+        #[test]
+        fn disjoint_union_keeps_points(rs in prop::collection::vec(rects(), 0..5)) {
+            let union = HyperRect::disjoint_union(rs.iter().copied());
+            let mut expected = rect_points(&rs);
+            expected.dedup();
+            prop_assert_eq!(rect_points(&union), expected);
+        }
+    }
+}
@@ -1,27 +1,69 @@
 use aoc_2dmap::prelude::Pos;
 use aoc_prelude::Itertools;
 use std::fmt::{Display, Formatter};
-use std::ptr;
 use std::str::FromStr;
 
+pub mod bench;
+pub mod check;
+pub mod cli;
+pub mod days;
+pub mod export;
+mod grid;
+pub mod input;
+mod interval;
+pub mod render;
+pub mod report;
+pub mod runner;
+mod solution;
+mod symmetry;
+
+pub use grid::{Grid, ParseMapError};
+pub use interval::{HyperRect, Interval, Piecewise};
+pub use solution::{Answer, Error, Result, Solution};
+pub use symmetry::{Symmetric, D4};
+
+/// Turns a day module's `parse`, `part1` and `part2` into a timed [`days::SolveFn`].
+#[macro_export]
+macro_rules! solver {
+    ($($day:ident)::+) => {
+        |input: &str| {
+            $crate::runner::time_phases(
+                input,
+                $($day)::+::parse,
+                |x| $($day)::+::part1(x),
+                |x| $($day)::+::part2(x),
+            )
+        }
+    };
+}
+
 /// Macro for solution timing
 /// Credits: <https://github.com/AxlLind>/
+///
+/// Optional extras follow the day, e.g. `day23, graph, render`:
+/// - `graph` supports `--export-graph`, with a `graph` function taking the parsed input;
+/// - `render` supports `--render`, with a `render` function taking the raw input;
+/// - `notes` prints what a `notes` function, taking the parsed input, says about the answers.
 #[macro_export]
 macro_rules! main {
-  ($($body:tt)+) => {
-    fn main() {
-      let now = std::time::Instant::now();
-      let (p1,p2) = { $($body)+ };
-      let elapsed = now.elapsed();
-      println!("Part one: {}", p1);
-      println!("Part two: {}", p2);
-      if elapsed.as_millis() > 0 {
-        println!("Time: {}ms", elapsed.as_millis());
-      } else {
-        println!("Time: {}μs", elapsed.as_micros());
-      }
-    }
-  }
+    (@extra $extras:ident, graph, $day:ident) => {
+        $extras.graph = Some(|input| $day::graph(&$day::parse(input)?));
+    };
+    (@extra $extras:ident, render, $day:ident) => {
+        $extras.render = Some($day::render);
+    };
+    (@extra $extras:ident, notes, $day:ident) => {
+        $extras.notes = Some(|input| $day::notes(&$day::parse(input)?));
+    };
+    ($($day:ident)::+ $(, $extra:ident)*) => {
+        fn main() {
+            use $($day)::+ as day;
+            #[allow(unused_mut)]
+            let mut extras = $crate::runner::Extras::default();
+            $($crate::main!(@extra extras, $extra, day);)*
+            $crate::runner::run_bin(env!("CARGO_BIN_NAME"), $crate::solver!(day), extras);
+        }
+    };
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -41,27 +83,21 @@ impl<const M: usize> Display for ConstMap<M> {
     }
 }
 
+/// Expects exactly `M` rows of `M` characters, see [`ParseMapError`].
 impl<const M: usize> FromStr for ConstMap<M, char> {
-    type Err = ();
+    type Err = ParseMapError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut inner = [[' '; M]; M];
-        for (i, c) in s[0..M * M].chars().enumerate() {
-            inner[i / M][i % M] = c;
-        }
-        Ok(ConstMap { inner })
+        Self::try_from(&s.parse::<Grid>()?)
     }
 }
 
+/// Expects exactly `M` rows of `M` digits, see [`ParseMapError`].
 impl<const M: usize> FromStr for ConstMap<M, u32> {
-    type Err = ();
+    type Err = ParseMapError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut inner = [[0; M]; M];
-        for (i, c) in s[0..M * M].chars().enumerate() {
-            inner[i / M][i % M] = c.to_digit(10).ok_or(())?;
-        }
-        Ok(ConstMap { inner })
+        Self::try_from(&s.parse::<Grid<u32>>()?)
     }
 }
 
@@ -80,23 +116,25 @@ impl<const M: usize, T: Copy> ConstMap<M, T> {
         }
         Some(self.inner[y][x])
     }
+}
 
-    pub fn transpose(&mut self) {
-        for r in 0..M {
-            for c in r..M {
-                // trust me
-                if c != r {
-                    unsafe {
-                        ptr::swap(&mut self.inner[r][c], &mut self.inner[c][r]);
-                    }
-                }
+impl<const M: usize, T: Copy> Symmetric for ConstMap<M, T> {
+    fn transpose(&mut self) {
+        for c in 1..M {
+            let (top, bottom) = self.inner.split_at_mut(c);
+            for (r, row) in top.iter_mut().enumerate() {
+                std::mem::swap(&mut row[c], &mut bottom[0][r]);
             }
         }
     }
 
-    pub fn flip_vertical(&mut self) {
+    fn flip_vertical(&mut self) {
         for row in &mut self.inner {
             row.reverse();
         }
     }
+
+    fn flip_horizontal(&mut self) {
+        self.inner.reverse();
+    }
 }
@@ -1,30 +1,71 @@
-use std::error::Error;
-use std::process::Command;
+use aoc_2023::bench;
+use aoc_2023::check::Check;
+use aoc_2023::cli::{flag_value, has_flag, parse_days};
+use aoc_2023::days::{self, Day, DAYS};
+use aoc_2023::input::{default_path, Source};
+use aoc_2023::report::{render_all, Format, Record};
+use aoc_2023::runner::{bench_days, check_record, print_matrix, run_day, run_parallel};
+use aoc_2023::Result;
+use std::process;
 
-fn extract_microseconds(output: &str) -> Result<usize, Box<dyn Error>> {
-    let out = output.lines().last().unwrap();
-    let time = if out.ends_with("ms") {
-        out["Time: ".len()..out.len() - 2].parse::<usize>()? * 1000
-    } else {
-        out["Time: ".len()..out.len() - 3].parse::<usize>()?
+fn selected_days(args: &[String]) -> Result<Vec<Day>> {
+    let Some(spec) = flag_value(args.iter().cloned(), "--days") else {
+        return Ok(DAYS.to_vec());
     };
-    Ok(time)
+    parse_days(&spec)?
+        .into_iter()
+        .map(|num| days::get(num).ok_or_else(|| format!("day {num} is not available").into()))
+        .collect()
+}
+
+fn options(args: &[String]) -> Result<(Format, Vec<Day>, Option<bench::Options>)> {
+    let format = Format::from_args(args.iter().cloned())?;
+    Ok((
+        format,
+        selected_days(args)?,
+        bench::Options::from_args(args)?,
+    ))
 }
 
 fn main() {
-    let dot_dir = std::env::current_exe()
-        .map(|x| x.parent().unwrap().to_owned())
-        .expect("cannot get current exe");
-
-    let total_time = (1..=24)
-        .filter_map(|day_num| {
-            let cmd = Command::new(dot_dir.join(format!("day{day_num:0>2}")))
-                .output()
-                .unwrap();
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            println!("Day {day_num:0>2}:\n{output}");
-            extract_microseconds(&output).ok()
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let (format, days, bench) = options(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+
+    // benchmarks always run sequentially so the days don't compete for cores
+    if let Some(opts) = bench {
+        let days = days
+            .into_iter()
+            .map(|d| (d.num, d.solve, Source::File(default_path(d.num))));
+        let ok = bench_days(days, &opts, format);
+        process::exit(i32::from(!ok));
+    }
+
+    let records = if has_flag(args.clone(), "--parallel") {
+        run_parallel(days, run_day).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(2);
         })
-        .sum::<usize>();
-    println!("Total time: {}ms", total_time / 1000);
+    } else {
+        days.into_iter().map(run_day).collect()
+    };
+
+    print!("{}", render_all(&records, format));
+
+    let mut passed = records.iter().all(Record::is_ok);
+    if has_flag(args, "--check") {
+        let checks = records
+            .iter()
+            .map(|r| check_record(r, Some(&default_path(r.day))))
+            .collect::<Vec<_>>();
+        passed &= checks.iter().all(Check::passed);
+        print_matrix(&checks, format);
+    }
+
+    if !passed {
+        process::exit(1);
+    }
 }
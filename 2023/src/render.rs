@@ -0,0 +1,265 @@
+use crate::{ConstMap, Grid, Result};
+use aoc_2dmap::prelude::{Map, Pos};
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Pixels per tile side in PPM images.
+pub const PPM_SCALE: usize = 4;
+
+/// A map that can be drawn one character per tile.
+pub trait Tiles {
+    /// Width and height, in tiles.
+    fn dims(&self) -> (usize, usize);
+
+    fn tile(&self, p: Pos) -> char;
+}
+
+impl<const M: usize> Tiles for ConstMap<M> {
+    fn dims(&self) -> (usize, usize) {
+        (M, M)
+    }
+
+    fn tile(&self, p: Pos) -> char {
+        self.get(p).unwrap_or(' ')
+    }
+}
+
+impl Tiles for Grid {
+    fn dims(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn tile(&self, p: Pos) -> char {
+        self.get(p).unwrap_or(' ')
+    }
+}
+
+impl Tiles for Grid<u32> {
+    fn dims(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn tile(&self, p: Pos) -> char {
+        self.get(p)
+            .and_then(|d| char::from_digit(d, 10))
+            .unwrap_or(' ')
+    }
+}
+
+impl Tiles for Map<char> {
+    fn dims(&self) -> (usize, usize) {
+        (self.size.x as usize, self.size.y as usize)
+    }
+
+    fn tile(&self, p: Pos) -> char {
+        self.get(p).unwrap_or(' ')
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const RED: Self = Self(230, 60, 50);
+    pub const GREEN: Self = Self(80, 200, 90);
+    pub const YELLOW: Self = Self(240, 200, 60);
+    pub const BLUE: Self = Self(70, 130, 230);
+
+    /// Colour of an unhighlighted tile in images: dark floor, grey walls, light everything else.
+    fn of_tile(c: char) -> Self {
+        match c {
+            '.' | ' ' => Self(20, 20, 20),
+            '#' => Self(90, 90, 90),
+            _ => Self(170, 170, 170),
+        }
+    }
+}
+
+/// A snapshot of a map with some tiles highlighted by the solver.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    tiles: Vec<char>,
+    overlay: Vec<Option<Rgb>>,
+}
+
+impl Frame {
+    #[must_use]
+    pub fn new(map: &impl Tiles) -> Self {
+        let (width, height) = map.dims();
+        let tiles = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Pos::new(x, y)))
+            .map(|p| map.tile(p))
+            .collect();
+        Self {
+            width,
+            height,
+            tiles,
+            overlay: vec![None; width * height],
+        }
+    }
+
+    /// Colours the tile at `p`, ignoring positions off the map.
+    pub fn highlight(&mut self, p: Pos, color: Rgb) -> &mut Self {
+        if let (Ok(x), Ok(y)) = (usize::try_from(p.x), usize::try_from(p.y)) {
+            if x < self.width && y < self.height {
+                self.overlay[y * self.width + x] = Some(color);
+            }
+        }
+        self
+    }
+
+    pub fn highlight_all(&mut self, ps: impl IntoIterator<Item = Pos>, color: Rgb) -> &mut Self {
+        for p in ps {
+            self.highlight(p, color);
+        }
+        self
+    }
+
+    /// The map as text, highlighted tiles in bold 24-bit colour.
+    #[must_use]
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for (row, colors) in self
+            .tiles
+            .chunks(self.width.max(1))
+            .zip(self.overlay.chunks(self.width.max(1)))
+        {
+            for (&c, color) in row.iter().zip(colors) {
+                match color {
+                    Some(Rgb(r, g, b)) => {
+                        let _ = write!(out, "\x1b[1;38;2;{r};{g};{b}m{c}\x1b[0m");
+                    }
+                    None => out.push(c),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Binary PPM (P6) image, each tile a `scale` x `scale` square.
+    #[must_use]
+    pub fn ppm(&self, scale: usize) -> Vec<u8> {
+        let mut out =
+            format!("P6\n{} {}\n255\n", self.width * scale, self.height * scale).into_bytes();
+        for y in 0..self.height * scale {
+            for x in 0..self.width * scale {
+                let idx = (y / scale) * self.width + x / scale;
+                let Rgb(r, g, b) = self.overlay[idx].unwrap_or(Rgb::of_tile(self.tiles[idx]));
+                out.extend([r, g, b]);
+            }
+        }
+        out
+    }
+}
+
+/// Writes `frames` to `path`: ANSI text for `-` (to `console`) or a `.txt` file, PPM images
+/// otherwise.
+///
+/// Text frames are separated by a clear-screen sequence, so they play back as an animation
+/// in a terminal. A single image goes to `path` itself, a sequence to `path` with the frame
+/// number before the extension: `out.ppm` becomes `out-0000.ppm`, `out-0001.ppm`, ...
+///
+/// # Errors
+///
+/// Fails on an unknown extension, when there is nothing to draw, or if a file or the console
+/// can't be written.
+pub fn write(frames: &[Frame], path: &Path, console: &mut impl io::Write) -> Result<()> {
+    if frames.is_empty() {
+        return Err("nothing to render".into());
+    }
+    let write = |path: &Path, data: &[u8]| {
+        std::fs::write(path, data).map_err(|e| format!("{}: {e}", path.display()))
+    };
+    let text = || {
+        frames
+            .iter()
+            .map(Frame::ansi)
+            .collect::<Vec<_>>()
+            .join("\x1b[2J\x1b[H")
+    };
+
+    if path == Path::new("-") {
+        console
+            .write_all(text().as_bytes())
+            .map_err(|e| format!("console: {e}"))?;
+        return Ok(());
+    }
+    match path.extension().and_then(|x| x.to_str()) {
+        Some("txt") => write(path, text().as_bytes())?,
+        Some("ppm") if frames.len() == 1 => write(path, &frames[0].ppm(PPM_SCALE))?,
+        Some("ppm") => {
+            for (i, frame) in frames.iter().enumerate() {
+                write(&numbered(path, i), &frame.ppm(PPM_SCALE))?;
+            }
+        }
+        _ => {
+            return Err(format!(
+                "cannot tell the image format of {}, use -, .txt or .ppm",
+                path.display()
+            )
+            .into())
+        }
+    }
+    Ok(())
+}
+
+/// `dir/out.ppm` as `dir/out-0007.ppm`.
+fn numbered(path: &Path, i: usize) -> PathBuf {
+    let stem = path.file_stem().and_then(|x| x.to_str()).unwrap_or("frame");
+    path.with_file_name(format!("{stem}-{i:04}.ppm"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        let grid = "#.\n.O".parse::<Grid>().unwrap();
+        let mut frame = Frame::new(&grid);
+        frame.highlight(Pos::new(1, 0), Rgb::RED);
+        frame.highlight(Pos::new(5, 5), Rgb::RED);
+        frame
+    }
+
+    #[test]
+    fn ansi() {
+        assert_eq!(frame().ansi(), "#\x1b[1;38;2;230;60;50m.\x1b[0m\n.O\n");
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = frame().ppm(2);
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        // first row: two wall pixels, then two highlighted ones
+        assert_eq!(
+            &pixels[..12],
+            &[90, 90, 90, 90, 90, 90, 230, 60, 50, 230, 60, 50]
+        );
+    }
+
+    #[test]
+    fn dash_goes_to_the_console() {
+        let mut console = Vec::new();
+        write(&[frame(), frame()], Path::new("-"), &mut console).unwrap();
+        let ansi = frame().ansi();
+        assert_eq!(
+            String::from_utf8(console).unwrap(),
+            format!("{ansi}\x1b[2J\x1b[H{ansi}")
+        );
+    }
+
+    #[test]
+    fn frame_file_names() {
+        assert_eq!(
+            numbered(Path::new("out/tilt.ppm"), 7),
+            PathBuf::from("out/tilt-0007.ppm")
+        );
+    }
+}
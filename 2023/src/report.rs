@@ -0,0 +1,292 @@
+use crate::cli::flag_value;
+use crate::runner::Run;
+use crate::{Error, Result};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+const FORMAT_FLAG: &str = "--format";
+
+/// Column order of the CSV output.
+pub const CSV_HEADER: &str =
+    "day,part_one,part_two,time_ns,parse_ns,part1_ns,part2_ns,status,error";
+
+/// How results get printed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!("unknown format: {other} (expected text, json or csv)").into()),
+        }
+    }
+}
+
+impl Format {
+    /// Reads `--format <text|json|csv>`, defaulting to text.
+    ///
+    /// # Errors
+    ///
+    /// Fails on an unknown format name.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        flag_value(args, FORMAT_FLAG).map_or(Ok(Format::Text), |x| x.parse())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Error(String),
+}
+
+/// Outcome of running a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub p1: String,
+    pub p2: String,
+    /// Total of the three phases below.
+    pub time_ns: u64,
+    pub parse_ns: u64,
+    pub part1_ns: u64,
+    pub part2_ns: u64,
+    pub status: Status,
+}
+
+impl Record {
+    #[must_use]
+    pub fn new(day: u8, run: Run) -> Self {
+        let (p1, p2, status) = match run.result {
+            Ok(solution) => (solution.p1.to_string(), solution.p2.to_string(), Status::Ok),
+            Err(e) => (String::new(), String::new(), Status::Error(e.to_string())),
+        };
+        let ns = |x: Duration| x.as_nanos() as u64;
+        Self {
+            day,
+            p1,
+            p2,
+            time_ns: ns(run.phases.total()),
+            parse_ns: ns(run.phases.parse),
+            part1_ns: ns(run.phases.part1),
+            part2_ns: ns(run.phases.part2),
+            status,
+        }
+    }
+
+    pub fn failed<E: Display>(day: u8, error: E) -> Self {
+        Self {
+            day,
+            p1: String::new(),
+            p2: String::new(),
+            time_ns: 0,
+            parse_ns: 0,
+            part1_ns: 0,
+            part2_ns: 0,
+            status: Status::Error(error.to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.status == Status::Ok
+    }
+
+    /// Human-readable output, as printed by the day binaries.
+    #[must_use]
+    pub fn text(&self) -> String {
+        match &self.status {
+            Status::Ok => format!(
+                "Part one: {}\nPart two: {}\nTime: {} (parse {}, part one {}, part two {})\n",
+                self.p1,
+                self.p2,
+                fmt_time(self.time_ns),
+                fmt_time(self.parse_ns),
+                fmt_time(self.part1_ns),
+                fmt_time(self.part2_ns),
+            ),
+            Status::Error(e) => format!("Error: {e}\n"),
+        }
+    }
+
+    #[must_use]
+    pub fn json(&self) -> String {
+        let (status, error) = match &self.status {
+            Status::Ok => ("ok", "null".to_owned()),
+            Status::Error(e) => ("error", json_str(e)),
+        };
+        let answer = |x: &str| {
+            if self.is_ok() {
+                json_str(x)
+            } else {
+                "null".to_owned()
+            }
+        };
+        format!(
+            concat!(
+                r#"{{"day":{},"part_one":{},"part_two":{},"time_ns":{},"#,
+                r#""parse_ns":{},"part1_ns":{},"part2_ns":{},"status":"{}","error":{}}}"#,
+            ),
+            self.day,
+            answer(&self.p1),
+            answer(&self.p2),
+            self.time_ns,
+            self.parse_ns,
+            self.part1_ns,
+            self.part2_ns,
+            status,
+            error,
+        )
+    }
+
+    #[must_use]
+    pub fn csv(&self) -> String {
+        let (status, error) = match &self.status {
+            Status::Ok => ("ok", ""),
+            Status::Error(e) => ("error", e.as_str()),
+        };
+        [
+            self.day.to_string(),
+            csv_field(&self.p1),
+            csv_field(&self.p2),
+            self.time_ns.to_string(),
+            self.parse_ns.to_string(),
+            self.part1_ns.to_string(),
+            self.part2_ns.to_string(),
+            status.to_owned(),
+            csv_field(error),
+        ]
+        .join(",")
+    }
+
+    /// Output of a single day binary.
+    #[must_use]
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.text(),
+            Format::Json => format!("{}\n", self.json()),
+            Format::Csv => format!("{CSV_HEADER}\n{}\n", self.csv()),
+        }
+    }
+}
+
+/// Output of the multi-day runner.
+#[must_use]
+pub fn render_all(records: &[Record], format: Format) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Text => {
+            for r in records {
+                let _ = writeln!(out, "Day {:0>2}:\n{}", r.day, r.text());
+            }
+            let total = records.iter().map(|r| r.time_ns).sum::<u64>();
+            let _ = writeln!(out, "Total time: {}ms", total / 1_000_000);
+            let failed = records
+                .iter()
+                .filter(|r| !r.is_ok())
+                .map(|r| format!("{:0>2}", r.day))
+                .collect::<Vec<_>>();
+            if !failed.is_empty() {
+                let _ = writeln!(out, "Failed days: {}", failed.join(", "));
+            }
+        }
+        Format::Json => {
+            let rows = records.iter().map(Record::json).collect::<Vec<_>>();
+            let _ = writeln!(out, "[{}]", rows.join(","));
+        }
+        Format::Csv => {
+            let _ = writeln!(out, "{CSV_HEADER}");
+            for r in records {
+                let _ = writeln!(out, "{}", r.csv());
+            }
+        }
+    }
+    out
+}
+
+fn fmt_time(time_ns: u64) -> String {
+    let elapsed = Duration::from_nanos(time_ns);
+    if elapsed.as_millis() > 0 {
+        format!("{}ms", elapsed.as_millis())
+    } else {
+        format!("{}μs", elapsed.as_micros())
+    }
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(msg: &str) -> Record {
+        Record::failed(7, msg)
+    }
+
+    #[test]
+    fn json_escapes_error_messages() {
+        let r = failed("bad \"hand\", line 3\n\tAKQ\\J\u{1}");
+        assert_eq!(
+            r.json(),
+            concat!(
+                r#"{"day":7,"part_one":null,"part_two":null,"time_ns":0,"parse_ns":0,"#,
+                r#""part1_ns":0,"part2_ns":0,"status":"error","#,
+                r#""error":"bad \"hand\", line 3\n\tAKQ\\J\u0001"}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_that_need_it() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), r#""a,b""#);
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(
+            failed("bad \"hand\", line 3\nAKQJ").csv(),
+            "7,,,0,0,0,0,error,\"bad \"\"hand\"\", line 3\nAKQJ\""
+        );
+    }
+
+    #[test]
+    fn csv_has_one_field_per_header_column() {
+        let columns = CSV_HEADER.split(',').count();
+        assert_eq!(failed("plain").csv().split(',').count(), columns);
+    }
+}
@@ -0,0 +1,271 @@
+use crate::bench::{self, Measurement};
+use crate::check::{render_matrix, Check, Expected};
+use crate::cli::{flag_value, has_flag};
+use crate::days::Day;
+use crate::export;
+use crate::input::{self, day_from_bin, Source};
+use crate::render::{self, Frame};
+use crate::report::{Format, Record};
+use crate::{Answer, Result, Solution};
+use rayon::prelude::*;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{env, io, process};
+
+const CHECK_FLAG: &str = "--check";
+const EXPORT_FLAG: &str = "--export-graph";
+const RENDER_FLAG: &str = "--render";
+
+/// Builds a day's puzzle graph from its raw input, for `--export-graph`.
+pub type GraphFn = fn(&str) -> Result<export::Graph>;
+
+/// Draws a day's solution on its map, for `--render`.
+pub type RenderFn = fn(&str) -> Result<Vec<Frame>>;
+
+/// Explains a day's answers from its raw input, printed after them.
+pub type NotesFn = fn(&str) -> Result<String>;
+
+/// Writes one of the [`Extras`] to a file.
+type OutputFn = fn(&Extras, &str, &Path, Format) -> Result<()>;
+
+/// What a day binary can do besides solving, see [`main!`](crate::main).
+#[derive(Copy, Clone, Default)]
+pub struct Extras {
+    pub graph: Option<GraphFn>,
+    pub render: Option<RenderFn>,
+    pub notes: Option<NotesFn>,
+}
+
+/// Time spent in each phase of a day.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Phases {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Phases {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Outcome of a timed run. Phases after a failing one are left at zero.
+#[derive(Debug)]
+pub struct Run {
+    pub result: Result<Solution>,
+    pub phases: Phases,
+}
+
+fn timed<T>(f: impl FnOnce() -> T, elapsed: &mut Duration) -> T {
+    let now = Instant::now();
+    let res = f();
+    *elapsed = now.elapsed();
+    res
+}
+
+/// Runs `parse` on `input`, then both parts on the parsed input, timing each phase.
+/// Usually called through [`solver!`](crate::solver).
+pub fn time_phases<'a, I, A, B>(
+    input: &'a str,
+    parse: impl FnOnce(&'a str) -> Result<I>,
+    part1: impl FnOnce(&I) -> Result<A>,
+    part2: impl FnOnce(&I) -> Result<B>,
+) -> Run
+where
+    A: Into<Answer>,
+    B: Into<Answer>,
+{
+    let mut phases = Phases::default();
+    let run = || -> Result<Solution> {
+        let parsed = timed(|| parse(input), &mut phases.parse)?;
+        let p1 = timed(|| part1(&parsed), &mut phases.part1)?;
+        let p2 = timed(|| part2(&parsed), &mut phases.part2)?;
+        Ok((p1, p2).into())
+    };
+    Run {
+        result: run(),
+        phases,
+    }
+}
+
+/// Runs a registered day on its checked-in input.
+#[must_use]
+pub fn run_day(day: Day) -> Record {
+    match Source::File(input::default_path(day.num)).read() {
+        Ok(input) => Record::new(day.num, (day.solve)(&input)),
+        Err(e) => Record::failed(day.num, format!("failed to read input: {e}")),
+    }
+}
+
+/// Stack of each `--parallel` worker: as much as a main thread gets, since some days recurse
+/// deep enough to overflow rayon's default.
+const PARALLEL_STACK: usize = 8 << 20;
+
+/// Runs `run` on all of `days` at once, keeping their order.
+///
+/// # Errors
+/// If the worker threads can't be started.
+pub fn run_parallel(
+    days: Vec<Day>,
+    run: impl Fn(Day) -> Record + Send + Sync,
+) -> Result<Vec<Record>> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .stack_size(PARALLEL_STACK)
+        .build()?;
+    Ok(pool.install(|| days.into_par_iter().map(run).collect()))
+}
+
+/// Compares `record` against the answers recorded next to `input_path`.
+#[must_use]
+pub fn check_record(record: &Record, input_path: Option<&Path>) -> Check {
+    let expected = input_path.and_then(|path| {
+        Expected::load(path).unwrap_or_else(|e| {
+            eprintln!("cannot read answers for day {:0>2}: {e}", record.day);
+            None
+        })
+    });
+    Check::new(record, expected.as_ref())
+}
+
+/// Entry point of every day binary, see [`main!`](crate::main).
+///
+/// Loads the input, runs `solve` and prints the record in the requested `--format`.
+/// With `--check`, also compares the answers against the recorded ones.
+/// With `--bench N`, benchmarks the day instead, see [`bench_days`].
+/// With `--export-graph <file>`, also writes the day's `graph` as DOT or `GraphML`.
+/// With `--render <file>`, also draws the day's solution, see [`render::write`].
+/// Exits non-zero if anything fails along the way.
+pub fn run_bin<F: Fn(&str) -> Run>(bin_name: &str, solve: F, extras: Extras) {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let format = Format::from_args(args.clone()).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    let Some(day) = day_from_bin(bin_name) else {
+        eprintln!("cannot infer day from binary name: {bin_name}");
+        process::exit(2);
+    };
+
+    let source = Source::resolve(day, args.clone());
+    match bench::Options::from_args(&args) {
+        Ok(Some(opts)) => {
+            let ok = bench_days([(day, solve, source)], &opts, format);
+            process::exit(i32::from(!ok));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(2);
+        }
+    }
+
+    let input = source.read();
+    let outputs: [(_, OutputFn, _); 2] = [
+        (EXPORT_FLAG, export_graph, "export the graph"),
+        (RENDER_FLAG, render_frames, "render"),
+    ];
+    for (flag, output, what) in outputs {
+        let Some(path) = flag_value(args.clone(), flag) else {
+            continue;
+        };
+        let done = match &input {
+            Ok(input) => output(&extras, input, Path::new(&path), format),
+            Err(e) => Err(format!("failed to read input: {e}").into()),
+        };
+        if let Err(e) = done {
+            eprintln!("cannot {what} of day {day:0>2}: {e}");
+            process::exit(1);
+        }
+    }
+
+    let record = match &input {
+        Ok(input) => Record::new(day, solve(input)),
+        Err(e) => Record::failed(day, format!("failed to read input: {e}")),
+    };
+
+    // failed records still go to stdout in the machine-readable formats
+    if record.is_ok() || format != Format::Text {
+        print!("{}", record.render(format));
+    }
+    if !record.is_ok() {
+        eprint!("{}", record.text());
+    }
+    if let (true, Some(notes), Ok(input)) = (record.is_ok(), extras.notes, &input) {
+        match notes(input) {
+            Ok(notes) if format == Format::Text => println!("{notes}"),
+            Ok(notes) => eprintln!("{notes}"),
+            Err(e) => eprintln!("no notes for day {day:0>2}: {e}"),
+        }
+    }
+
+    let mut passed = record.is_ok();
+    if has_flag(args, CHECK_FLAG) {
+        let input_path = match &source {
+            Source::File(path) => Some(path.as_path()),
+            Source::Stdin => None,
+        };
+        let check = check_record(&record, input_path);
+        passed &= check.passed();
+        print_matrix(&[check], format);
+    }
+
+    if !passed {
+        process::exit(1);
+    }
+}
+
+fn export_graph(extras: &Extras, input: &str, path: &Path, _: Format) -> Result<()> {
+    let graph = extras.graph.ok_or("this day has no graph")?;
+    graph(input)?.write(path)
+}
+
+/// Draws on stderr for `--render -` if stdout is reserved for machine-readable output.
+fn render_frames(extras: &Extras, input: &str, path: &Path, format: Format) -> Result<()> {
+    let render = extras.render.ok_or("this day has nothing to draw")?;
+    let frames = render(input)?;
+    if format == Format::Text {
+        render::write(&frames, path, &mut io::stdout())
+    } else {
+        render::write(&frames, path, &mut io::stderr())
+    }
+}
+
+/// Prints the check matrix, on stderr if stdout is reserved for machine-readable output.
+pub fn print_matrix(checks: &[Check], format: Format) {
+    let matrix = render_matrix(checks);
+    if format == Format::Text {
+        print!("\n{matrix}");
+    } else {
+        eprint!("{matrix}");
+    }
+}
+
+/// Benchmarks `days` one after the other, then prints the timings and compares them against
+/// the baseline, see [`bench::report`].
+/// Returns whether every day ran and none got slower than the threshold.
+pub fn bench_days<F, I>(days: I, opts: &bench::Options, format: Format) -> bool
+where
+    F: Fn(&str) -> Run,
+    I: IntoIterator<Item = (u8, F, Source)>,
+{
+    let mut ok = true;
+    let mut measurements = Vec::<Measurement>::new();
+    for (day, solve, source) in days {
+        match bench::bench(day, solve, &source, opts) {
+            Ok(m) => measurements.extend(m),
+            Err(e) => {
+                eprintln!("Day {day:0>2}: {e}");
+                ok = false;
+            }
+        }
+    }
+    match bench::report(&measurements, opts, format) {
+        Ok(within_threshold) => ok && within_threshold,
+        Err(e) => {
+            eprintln!("{e}");
+            false
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use aoc_prelude::BigInt;
+use std::fmt::{Display, Formatter};
+
+/// Boxed error so that every day can bubble parse failures up with `?`.
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Result type of a day's `solve` function.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A single part's answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Int(i128),
+    Big(BigInt),
+    Float(f64),
+    Text(String),
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(x) => x.fmt(f),
+            Answer::Big(x) => x.fmt(f),
+            Answer::Float(x) => x.fmt(f),
+            Answer::Text(x) => x.fmt(f),
+            Answer::Unsolved => f.write_str("unsolved"),
+        }
+    }
+}
+
+macro_rules! int_answer {
+    ($($t:ty),+) => {
+        $(impl From<$t> for Answer {
+            fn from(x: $t) -> Self {
+                Answer::Int(x.into())
+            }
+        })+
+    };
+}
+
+int_answer!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<usize> for Answer {
+    fn from(x: usize) -> Self {
+        Answer::Int(x as i128)
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(x: BigInt) -> Self {
+        Answer::Big(x)
+    }
+}
+
+impl From<f64> for Answer {
+    fn from(x: f64) -> Self {
+        Answer::Float(x)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(x: String) -> Self {
+        Answer::Text(x)
+    }
+}
+
+/// Both parts' answers for a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution {
+    pub p1: Answer,
+    pub p2: Answer,
+}
+
+impl<A: Into<Answer>, B: Into<Answer>> From<(A, B)> for Solution {
+    fn from((p1, p2): (A, B)) -> Self {
+        Self {
+            p1: p1.into(),
+            p2: p2.into(),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+/// The eight symmetries of a square: four rotations, and four mirrorings.
+///
+/// Each one is a mirroring left to right (or not) followed by some clockwise quarter turns,
+/// which is how [`D4::then`] and [`D4::inverse`] work them out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum D4 {
+    Identity,
+    RotateCw,
+    Rotate180,
+    RotateCcw,
+    /// Left to right, see [`Symmetric::flip_vertical`].
+    FlipVertical,
+    /// Top to bottom, see [`Symmetric::flip_horizontal`].
+    FlipHorizontal,
+    /// About the main diagonal.
+    Transpose,
+    /// About the other diagonal.
+    AntiTranspose,
+}
+
+impl D4 {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::RotateCw,
+        Self::Rotate180,
+        Self::RotateCcw,
+        Self::FlipVertical,
+        Self::FlipHorizontal,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// Whether it mirrors, and how many quarter turns follow.
+    fn parts(self) -> (bool, u8) {
+        match self {
+            Self::Identity => (false, 0),
+            Self::RotateCw => (false, 1),
+            Self::Rotate180 => (false, 2),
+            Self::RotateCcw => (false, 3),
+            Self::FlipVertical => (true, 0),
+            Self::AntiTranspose => (true, 1),
+            Self::FlipHorizontal => (true, 2),
+            Self::Transpose => (true, 3),
+        }
+    }
+
+    fn from_parts(mirror: bool, turns: u8) -> Self {
+        match (mirror, turns % 4) {
+            (false, 0) => Self::Identity,
+            (false, 1) => Self::RotateCw,
+            (false, 2) => Self::Rotate180,
+            (false, _) => Self::RotateCcw,
+            (true, 0) => Self::FlipVertical,
+            (true, 1) => Self::AntiTranspose,
+            (true, 2) => Self::FlipHorizontal,
+            (true, _) => Self::Transpose,
+        }
+    }
+
+    /// `self` followed by `next`.
+    #[must_use]
+    pub fn then(self, next: Self) -> Self {
+        let ((m1, r1), (m2, r2)) = (self.parts(), next.parts());
+        // mirroring after a turn is the same as mirroring before the opposite turn
+        let r1 = if m2 { 4 - r1 } else { r1 };
+        Self::from_parts(m1 ^ m2, r1 + r2)
+    }
+
+    /// The symmetry undoing this one.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        match self.parts() {
+            (false, turns) => Self::from_parts(false, 4 - turns),
+            // mirrorings undo themselves
+            (true, _) => self,
+        }
+    }
+}
+
+/// In-place symmetries of a map, see [`D4`].
+///
+/// The quarter turns and the diagonal mirrorings swap width and height.
+pub trait Symmetric {
+    /// Swaps rows and columns.
+    fn transpose(&mut self);
+
+    /// Mirrors the map left to right, i.e. reverses every row.
+    fn flip_vertical(&mut self);
+
+    /// Mirrors the map top to bottom, i.e. reverses the order of the rows.
+    fn flip_horizontal(&mut self);
+
+    fn rotate_180(&mut self) {
+        self.flip_vertical();
+        self.flip_horizontal();
+    }
+
+    /// Rotates the map a quarter turn clockwise.
+    fn rotate_cw(&mut self) {
+        self.transpose();
+        self.flip_vertical();
+    }
+
+    /// Rotates the map a quarter turn counter-clockwise.
+    fn rotate_ccw(&mut self) {
+        self.transpose();
+        self.flip_horizontal();
+    }
+
+    fn apply(&mut self, symmetry: D4) {
+        match symmetry {
+            D4::Identity => {}
+            D4::RotateCw => self.rotate_cw(),
+            D4::Rotate180 => self.rotate_180(),
+            D4::RotateCcw => self.rotate_ccw(),
+            D4::FlipVertical => self.flip_vertical(),
+            D4::FlipHorizontal => self.flip_horizontal(),
+            D4::Transpose => self.transpose(),
+            D4::AntiTranspose => {
+                self.transpose();
+                self.rotate_180();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConstMap, Grid};
+    use proptest::prelude::*;
+
+    fn grids() -> impl Strategy<Value = Grid<u8>> {
+        (1..8usize, 1..8usize).prop_flat_map(|(w, h)| {
+            prop::collection::vec(any::<u8>(), w * h)
+                .prop_map(move |cells| Grid::new(w, h, cells).unwrap())
+        })
+    }
+
+    fn const_maps() -> impl Strategy<Value = ConstMap<5, u8>> {
+        prop::array::uniform5(prop::array::uniform5(any::<u8>()))
+            .prop_map(|inner| ConstMap { inner })
+    }
+
+    fn symmetries() -> impl Strategy<Value = D4> {
+        prop::sample::select(D4::ALL.to_vec())
+    }
+
+    fn applied<S: Symmetric + Clone>(map: &S, symmetries: &[D4]) -> S {
+        let mut map = map.clone();
+        for &s in symmetries {
+            map.apply(s);
+        }
+        map
+    }
+
+    #[test]
+    fn group_table() {
+        use D4::*;
+        assert_eq!(RotateCw.then(RotateCw), Rotate180);
+        assert_eq!(RotateCcw.then(Rotate180), RotateCw);
+        assert_eq!(FlipVertical.then(FlipHorizontal), Rotate180);
+        assert_eq!(Transpose.then(FlipVertical), RotateCw);
+        assert_eq!(Transpose.then(FlipHorizontal), RotateCcw);
+        assert_eq!(Transpose.then(Rotate180), AntiTranspose);
+        assert_eq!(RotateCw.inverse(), RotateCcw);
+        for s in D4::ALL {
+            assert_eq!(s.then(s.inverse()), Identity);
+            assert_eq!(Identity.then(s), s);
+        }
+    }
+
+    #[test]
+    fn const_map_transforms() {
+        let mut m = ConstMap::<3> {
+            inner: [['a', 'b', 'c'], ['d', 'e', 'f'], ['g', 'h', 'i']],
+        };
+        m.transpose();
+        assert_eq!(m.to_string(), "adg\nbeh\ncfi");
+        m.flip_horizontal();
+        assert_eq!(m.to_string(), "cfi\nbeh\nadg");
+        m.apply(D4::AntiTranspose);
+        assert_eq!(m.to_string(), "ghi\ndef\nabc");
+    }
+
+    proptest! {
+        #[test]
+        fn four_turns_are_nothing(g in grids(), m in const_maps()) {
+            prop_assert_eq!(&applied(&g, &[D4::RotateCw; 4]), &g);
+            prop_assert_eq!(&applied(&g, &[D4::RotateCcw; 4]), &g);
+            prop_assert_eq!(applied(&m, &[D4::RotateCw; 4]), m);
+            prop_assert_eq!(applied(&m, &[D4::RotateCcw; 4]), m);
+        }
+
+        #[test]
+        fn composition_matches_the_group(
+            g in grids(),
+            m in const_maps(),
+            a in symmetries(),
+            b in symmetries(),
+        ) {
+            prop_assert_eq!(applied(&g, &[a, b]), applied(&g, &[a.then(b)]));
+            prop_assert_eq!(applied(&m, &[a, b]), applied(&m, &[a.then(b)]));
+            prop_assert_eq!(&applied(&g, &[a, a.inverse()]), &g);
+        }
+
+        #[test]
+        fn grids_agree_with_const_maps(m in const_maps(), s in symmetries()) {
+            prop_assert_eq!(Grid::from(applied(&m, &[s])), applied(&Grid::from(m), &[s]));
+        }
+
+        #[test]
+        fn turns_swap_dimensions(g in grids(), s in symmetries()) {
+            let t = applied(&g, &[s]);
+            let swapped = matches!(
+                s,
+                D4::RotateCw | D4::RotateCcw | D4::Transpose | D4::AntiTranspose
+            );
+            let dims = if swapped { (g.height(), g.width()) } else { (g.width(), g.height()) };
+            prop_assert_eq!((t.width(), t.height()), dims);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Runs every day on the published examples in `tests/examples`.
+//!
+//! Each `NN.ex` comes with an `NN.ans` in the `--check` format: part one on the first line,
+//! part two on the second, blank if the example doesn't cover that part.
+
+use aoc_2023::check::Expected;
+use aoc_2023::days::*;
+use aoc_2023::report::Record;
+use aoc_2023::runner::run_parallel;
+use aoc_2023::{Answer, Result};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/examples")
+        .join(name)
+}
+
+fn input(name: &str) -> String {
+    fs::read_to_string(path(name)).unwrap_or_else(|e| panic!("{name}: {e}"))
+}
+
+fn example(name: &str) -> (String, Expected) {
+    let path = path(name);
+    let input = input(name);
+    let expected = Expected::load(&path)
+        .unwrap_or_else(|e| panic!("{name}: {e}"))
+        .unwrap_or_else(|| panic!("{name}: no answers recorded"));
+    (input, expected)
+}
+
+fn answer<A: Into<Answer>>(name: &str, part: &str, result: Result<A>) -> String {
+    match result {
+        Ok(x) => x.into().to_string(),
+        Err(e) => panic!("{name} part {part}: {e}"),
+    }
+}
+
+fn run_with_main_stack(f: impl FnOnce() + Send + 'static) {
+    let handle = thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(f)
+        .expect("failed to spawn test thread");
+    if let Err(e) = handle.join() {
+        std::panic::resume_unwind(e);
+    }
+}
+
+macro_rules! examples {
+    ($($(#[$attr:meta])* $day:ident: $($file:literal),+;)+) => {$(
+        #[test]
+        $(#[$attr])*
+        fn $day() {
+            // some solvers keep large arrays on the stack, give them as much as a main thread
+            run_with_main_stack(|| for name in [$($file),+] {
+                let (input, expected) = example(name);
+                let parsed = match $day::parse(&input) {
+                    Ok(x) => x,
+                    Err(e) => panic!("{name}: {e}"),
+                };
+                if let Some(p1) = expected.p1 {
+                    assert_eq!(answer(name, "one", $day::part1(&parsed)), p1, "{name} part one");
+                }
+                if let Some(p2) = expected.p2 {
+                    assert_eq!(answer(name, "two", $day::part2(&parsed)), p2, "{name} part two");
+                }
+            });
+        }
+    )+};
+}
+
+examples! {
+    day01: "01.ex", "01b.ex";
+    day02: "02.ex";
+    day03: "03.ex";
+    day04: "04.ex";
+    day05: "05.ex";
+    day06: "06.ex";
+    day07: "07.ex";
+    day08: "08.ex", "08b.ex";
+    day09: "09.ex";
+    day10: "10.ex", "10b.ex";
+    day11: "11.ex";
+    day12: "12.ex";
+    day13: "13.ex";
+    day14: "14.ex";
+    day15: "15.ex";
+    day16: "16.ex";
+    day17: "17.ex";
+    day18: "18.ex";
+    day19: "19.ex";
+    day20: "20.ex", "20b.ex";
+    day22: "22.ex";
+    day23: "23.ex";
+    day24: "24.ex";
+    day25: "25.ex";
+}
+
+#[test]
+fn every_day_in_parallel() {
+    let records = run_parallel(DAYS.to_vec(), |day| {
+        let input = input(&format!("{:02}.ex", day.num));
+        Record::new(day.num, (day.solve)(&input))
+    })
+    .unwrap();
+    let days = records.iter().map(|r| r.day).collect::<Vec<_>>();
+    assert_eq!(days, DAYS.map(|d| d.num));
+    for r in &records {
+        // day 20's examples have no `rx` module for part two to wait on
+        assert!(r.is_ok() || r.day == 20, "day {:02}: {r:?}", r.day);
+    }
+}
+
+#[test]
+fn rendering_to_the_console_keeps_stdout_machine_readable() {
+    let out = Command::new(env!("CARGO_BIN_EXE_day10"))
+        .arg("--input")
+        .arg(path("10.ex"))
+        .args(["--render", "-", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{out:?}");
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    assert!(stdout.starts_with(r#"{"day":10,"#), "{stdout}");
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+    assert!(String::from_utf8(out.stderr).unwrap().contains('\x1b'));
+}
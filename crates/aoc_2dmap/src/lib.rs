@@ -0,0 +1,209 @@
+pub mod prelude {
+    pub use crate::{Map, Pos};
+}
+
+use num_traits::NumCast;
+use std::ops::{Add, AddAssign, Sub};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Pos {
+    /// # Panics
+    ///
+    /// Panics if a coordinate doesn't fit in an `i32`.
+    #[must_use]
+    pub fn new<T: NumCast + Copy>(x: T, y: T) -> Self {
+        Self {
+            x: x.to_i32().unwrap(),
+            y: y.to_i32().unwrap(),
+        }
+    }
+
+    #[must_use]
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbors_simple(self) -> impl Iterator<Item = Pos> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(move |(dx, dy)| Pos::new(self.x + dx, self.y + dy))
+    }
+
+    pub fn neighbors_diag(self) -> impl Iterator<Item = Pos> {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+        ]
+        .into_iter()
+        .map(move |(dx, dy)| Pos::new(self.x + dx, self.y + dy))
+    }
+}
+
+impl AsRef<Pos> for Pos {
+    fn as_ref(&self) -> &Pos {
+        self
+    }
+}
+
+impl<T: NumCast + Copy> From<(T, T)> for Pos {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+    fn add(self, rhs: Pos) -> Pos {
+        Pos::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, rhs: Pos) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+    fn sub(self, rhs: Pos) -> Pos {
+        Pos::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map<T> {
+    pub size: Pos,
+    tiles: Vec<T>,
+}
+
+impl<T> Map<T> {
+    /// A `size.x` by `size.y` map, tiles given row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of tiles doesn't match the size.
+    pub fn new<S: Into<Pos>>(size: S, tiles: impl IntoIterator<Item = T>) -> Self {
+        let size = size.into();
+        let tiles = tiles.into_iter().collect::<Vec<_>>();
+        assert_eq!(tiles.len(), (size.x * size.y) as usize, "map size mismatch");
+        Self { size, tiles }
+    }
+
+    fn idx(&self, p: Pos) -> Option<usize> {
+        if p.x < 0 || p.y < 0 || p.x >= self.size.x || p.y >= self.size.y {
+            None
+        } else {
+            Some((p.y * self.size.x + p.x) as usize)
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Pos> {
+        let size = self.size;
+        (0..size.y).flat_map(move |y| (0..size.x).map(move |x| Pos::new(x, y)))
+    }
+
+    #[must_use]
+    pub fn get_ref(&self, p: Pos) -> Option<&T> {
+        self.idx(p).map(|i| &self.tiles[i])
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `p` is off the map.
+    #[must_use]
+    pub fn get_unchecked_ref(&self, p: Pos) -> &T {
+        self.get_ref(p).expect("out of bounds")
+    }
+
+    pub fn set(&mut self, p: Pos, val: T) {
+        if let Some(i) = self.idx(p) {
+            self.tiles[i] = val;
+        }
+    }
+}
+
+impl<T: Copy> Map<T> {
+    #[must_use]
+    pub fn get(&self, p: Pos) -> Option<T> {
+        self.get_ref(p).copied()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `p` is off the map.
+    #[must_use]
+    pub fn get_unchecked(&self, p: Pos) -> T {
+        *self.get_unchecked_ref(p)
+    }
+
+    pub fn get_row(&self, y: i32) -> impl Iterator<Item = T> + '_ {
+        (0..self.size.x).map(move |x| self.get_unchecked(Pos::new(x, y)))
+    }
+
+    pub fn get_col(&self, x: i32) -> impl Iterator<Item = T> + '_ {
+        (0..self.size.y).map(move |y| self.get_unchecked(Pos::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pos_arithmetic() {
+        let mut p = Pos::new(3, -2) + Pos::from((1u8, 4u8));
+        assert_eq!(p, Pos::new(4, 2));
+        p += Pos::new(-5, 0);
+        assert_eq!(p - Pos::new(1, 1), Pos::new(-2, 1));
+        assert_eq!(Pos::new(-7, 0).signum(), Pos::new(-1, 0));
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = Pos::new(1, 1);
+        assert_eq!(
+            p.neighbors_simple().collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)].map(Pos::from)
+        );
+        let diag = p.neighbors_diag().collect::<Vec<_>>();
+        assert_eq!(diag.len(), 8);
+        assert!(p.neighbors_simple().all(|n| diag.contains(&n)));
+        assert!(!diag.contains(&p));
+    }
+
+    #[test]
+    fn map_access() {
+        let mut map = Map::new((3, 2), "abcdef".chars());
+        assert_eq!(map.get(Pos::new(2, 1)), Some('f'));
+        assert_eq!(map.get(Pos::new(3, 0)), None);
+        assert_eq!(map.get(Pos::new(0, -1)), None);
+
+        map.set(Pos::new(1, 0), 'x');
+        map.set(Pos::new(9, 9), 'y');
+        assert_eq!(map.get_row(0).collect::<String>(), "axc");
+        assert_eq!(map.get_col(2).collect::<String>(), "cf");
+        assert_eq!(
+            map.iter().map(|p| map.get_unchecked(p)).collect::<String>(),
+            "axcdef"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "map size mismatch")]
+    fn map_size_mismatch() {
+        let _ = Map::new((2, 2), [0; 3]);
+    }
+}
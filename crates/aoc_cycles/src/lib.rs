@@ -0,0 +1,254 @@
+use hashbrown::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+/// Applies `f` to `state` `n` times, skipping ahead once the states start repeating.
+pub fn multicycle<S: Clone + Eq + Hash, F: Fn(&mut S)>(mut state: S, f: F, n: usize) -> S {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history: Vec<S> = Vec::new();
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let period = i - start;
+            return history[start + (n - start) % period].clone();
+        }
+        seen.insert(state.clone(), i);
+        history.push(state.clone());
+        f(&mut state);
+    }
+    state
+}
+
+/// The shape of the sequence `x0, f(x0), f(f(x0)), ...`: a tail of `mu` states that are never
+/// seen again, then a loop of `lambda` states repeating forever.
+///
+/// All three ways of finding it step through the sequence until it loops, so they never return
+/// if the sequence doesn't.
+pub struct Cycle<S, F> {
+    /// Length of the tail, i.e. the first step that comes round again.
+    pub mu: usize,
+    /// Length of the loop.
+    pub lambda: usize,
+    start: S,
+    f: F,
+    /// The first `mu + lambda` states, if the strategy kept them.
+    history: Vec<S>,
+}
+
+impl<S, F> Cycle<S, F>
+where
+    S: Clone + Eq,
+    F: Fn(&mut S),
+{
+    /// Floyd's tortoise and hare: constant memory, about three times `mu + lambda` steps.
+    pub fn floyd(start: S, f: F) -> Self {
+        let step = |mut s: S| {
+            f(&mut s);
+            s
+        };
+
+        let mut tortoise = step(start.clone());
+        let mut hare = step(tortoise.clone());
+        while tortoise != hare {
+            tortoise = step(tortoise);
+            hare = step(step(hare));
+        }
+
+        let mut mu = 0;
+        tortoise = start.clone();
+        while tortoise != hare {
+            tortoise = step(tortoise);
+            hare = step(hare);
+            mu += 1;
+        }
+
+        let mut lambda = 1;
+        hare = step(tortoise.clone());
+        while tortoise != hare {
+            hare = step(hare);
+            lambda += 1;
+        }
+        Self::new(mu, lambda, start, f, Vec::new())
+    }
+
+    /// Brent's teleporting tortoise: constant memory, fewer steps than Floyd.
+    pub fn brent(start: S, f: F) -> Self {
+        let step = |mut s: S| {
+            f(&mut s);
+            s
+        };
+
+        let (mut power, mut lambda) = (1, 1);
+        let mut tortoise = start.clone();
+        let mut hare = step(start.clone());
+        while tortoise != hare {
+            if power == lambda {
+                tortoise.clone_from(&hare);
+                power *= 2;
+                lambda = 0;
+            }
+            hare = step(hare);
+            lambda += 1;
+        }
+
+        tortoise = start.clone();
+        hare = start.clone();
+        for _ in 0..lambda {
+            hare = step(hare);
+        }
+        let mut mu = 0;
+        while tortoise != hare {
+            tortoise = step(tortoise);
+            hare = step(hare);
+            mu += 1;
+        }
+        Self::new(mu, lambda, start, f, Vec::new())
+    }
+
+    fn new(mu: usize, lambda: usize, start: S, f: F, history: Vec<S>) -> Self {
+        Self {
+            mu,
+            lambda,
+            start,
+            f,
+            history,
+        }
+    }
+
+    /// The earliest step with the same state as step `n`.
+    #[must_use]
+    pub fn step_at(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+
+    /// State after `n` steps, without taking more than `mu + lambda` of them.
+    #[must_use]
+    pub fn state_at(&self, n: usize) -> S {
+        let n = self.step_at(n);
+        if let Some(state) = self.history.get(n) {
+            return state.clone();
+        }
+        let mut state = self.start.clone();
+        for _ in 0..n {
+            (self.f)(&mut state);
+        }
+        state
+    }
+}
+
+impl<S, F> Cycle<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&mut S),
+{
+    /// Remembers every state: the fewest steps, and [`Cycle::state_at`] takes none at all.
+    pub fn hashed(start: S, f: F) -> Self {
+        let mut seen = HashMap::new();
+        let mut history = Vec::new();
+        let mut state = start.clone();
+        loop {
+            if let Some(&mu) = seen.get(&state) {
+                let lambda = history.len() - mu;
+                return Self::new(mu, lambda, start, f, history);
+            }
+            seen.insert(state.clone(), history.len());
+            history.push(state.clone());
+            f(&mut state);
+        }
+    }
+}
+
+impl<S, F> fmt::Debug for Cycle<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cycle")
+            .field("mu", &self.mu)
+            .field("lambda", &self.lambda)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, F> fmt::Display for Cycle<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "repeats from step {} every {} steps",
+            self.mu, self.lambda
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3 -> 10 -> 5 -> 16 -> 8 -> 4 -> 2 -> 1 -> 4 -> ..., a tail of five before a cycle of three.
+    fn collatz(x: &mut u64) {
+        *x = if x.is_multiple_of(2) {
+            *x / 2
+        } else {
+            3 * *x + 1
+        };
+    }
+
+    fn brute_force(mut x: u64, n: usize) -> u64 {
+        for _ in 0..n {
+            collatz(&mut x);
+        }
+        x
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for n in 0..20 {
+            assert_eq!(multicycle(3, collatz, n), brute_force(3, n), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn skips_ahead() {
+        assert_eq!(multicycle(3, collatz, 1_000_000_000), 1);
+        assert_eq!(multicycle(0u8, |x| *x = x.wrapping_add(1), 1_000), 232);
+    }
+
+    #[test]
+    fn strategies_agree() {
+        let cycles = [
+            Cycle::floyd(3, collatz),
+            Cycle::brent(3, collatz),
+            Cycle::hashed(3, collatz),
+        ];
+        for cycle in &cycles {
+            assert_eq!((cycle.mu, cycle.lambda), (5, 3), "{cycle:?}");
+            for n in 0..20 {
+                assert_eq!(cycle.state_at(n), brute_force(3, n), "{cycle:?}, n = {n}");
+            }
+            assert_eq!(cycle.state_at(1_000_000_000), 1);
+        }
+    }
+
+    #[test]
+    fn pure_loop() {
+        // x -> 5x mod 7 walks through every non-zero residue
+        let step = |x: &mut u32| *x = *x * 5 % 7;
+        for cycle in [
+            Cycle::floyd(1, step),
+            Cycle::brent(1, step),
+            Cycle::hashed(1, step),
+        ] {
+            assert_eq!((cycle.mu, cycle.lambda), (0, 6), "{cycle:?}");
+            assert_eq!(cycle.step_at(13), 1);
+            assert_eq!(cycle.state_at(13), 5);
+        }
+    }
+
+    #[test]
+    fn fixed_point() {
+        let cycle = Cycle::brent(7u8, |x| *x = (*x).saturating_sub(2));
+        assert_eq!((cycle.mu, cycle.lambda), (4, 1));
+        assert_eq!(cycle.state_at(100), 0);
+        assert_eq!(cycle.to_string(), "repeats from step 4 every 1 steps");
+    }
+}
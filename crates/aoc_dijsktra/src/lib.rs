@@ -0,0 +1,212 @@
+use hashbrown::HashMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A move from one state to the next.
+pub trait Transform<G, C> {
+    fn cost(&self) -> C;
+    fn transform(&self, game_state: &G) -> G;
+}
+
+/// A node of the search space, see [`search`].
+pub trait GameState<Ctx, C>: Sized {
+    type Steps: IntoIterator<Item: Transform<Self, C>>;
+
+    fn accept(&self, cost: C, ctx: &mut Ctx) -> bool;
+    fn steps(&self, ctx: &mut Ctx) -> Self::Steps;
+
+    /// Lower bound on the cost left to reach an accepted state, which turns the search into A*.
+    /// It must never overestimate, or the path found might not be the cheapest.
+    /// Zero by default, for a plain Dijkstra.
+    fn heuristic(&self, _ctx: &mut Ctx) -> C
+    where
+        C: Default,
+    {
+        C::default()
+    }
+}
+
+/// A move out of `G`, as handed out by [`GameState::steps`].
+pub type Step<G, Ctx, C> = <<G as GameState<Ctx, C>>::Steps as IntoIterator>::Item;
+
+/// The cheapest way from a start state to an accepted one.
+#[derive(Clone, Debug)]
+pub struct Path<G, M, C> {
+    pub cost: C,
+    /// Every state along the way, from the start to the accepted one.
+    pub states: Vec<G>,
+    /// The move taken out of each state but the last, so `moves[i]` leads from `states[i]` to
+    /// `states[i + 1]`.
+    pub moves: Vec<M>,
+}
+
+/// What a [`search`] found, and how much work it took.
+#[derive(Clone, Debug)]
+pub struct Outcome<G, M, C> {
+    pub path: Option<Path<G, M, C>>,
+    /// Number of states expanded.
+    pub visited: usize,
+}
+
+/// Cheapest path from any of `starts` to an accepted state: A* with the states' heuristic,
+/// Dijkstra if they don't have one.
+pub fn search<Ctx, C, G>(
+    starts: impl IntoIterator<Item = G>,
+    ctx: &mut Ctx,
+) -> Outcome<G, Step<G, Ctx, C>, C>
+where
+    G: GameState<Ctx, C> + Copy + Ord + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    let mut dist = HashMap::new();
+    // how each state was reached on its cheapest known way, starts have no entry
+    let mut came_from = HashMap::<G, (G, Step<G, Ctx, C>)>::new();
+    let mut q = BinaryHeap::new();
+    for start in starts {
+        dist.insert(start, C::default());
+        q.push(Reverse((start.heuristic(ctx), C::default(), start)));
+    }
+
+    let mut visited = 0;
+    while let Some(Reverse((_, cost, state))) = q.pop() {
+        if dist.get(&state).is_some_and(|&d| cost > d) {
+            continue;
+        }
+        if state.accept(cost, ctx) {
+            let (mut states, mut moves) = (vec![state], Vec::new());
+            let mut cur = state;
+            while let Some((prev, step)) = came_from.remove(&cur) {
+                states.push(prev);
+                moves.push(step);
+                cur = prev;
+            }
+            states.reverse();
+            moves.reverse();
+            return Outcome {
+                path: Some(Path {
+                    cost,
+                    states,
+                    moves,
+                }),
+                visited,
+            };
+        }
+        visited += 1;
+        for step in state.steps(ctx) {
+            let next = step.transform(&state);
+            let next_cost = cost + step.cost();
+            if dist.get(&next).is_none_or(|&d| next_cost < d) {
+                dist.insert(next, next_cost);
+                q.push(Reverse((next_cost + next.heuristic(ctx), next_cost, next)));
+                came_from.insert(next, (state, step));
+            }
+        }
+    }
+    Outcome {
+        path: None,
+        visited,
+    }
+}
+
+/// Single-start shorthands for [`search`].
+pub trait Dijsktra<Ctx, C>: GameState<Ctx, C> {
+    fn dijsktra(self, ctx: &mut Ctx) -> Option<C>;
+    fn shortest_path(self, ctx: &mut Ctx) -> Option<Path<Self, Step<Self, Ctx, C>, C>>;
+}
+
+impl<Ctx, C, G> Dijsktra<Ctx, C> for G
+where
+    G: GameState<Ctx, C> + Copy + Ord + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+{
+    fn dijsktra(self, ctx: &mut Ctx) -> Option<C> {
+        self.shortest_path(ctx).map(|p| p.cost)
+    }
+
+    fn shortest_path(self, ctx: &mut Ctx) -> Option<Path<Self, Step<Self, Ctx, C>, C>> {
+        search([self], ctx).path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walking along a number line, one or three steps at a time for 1 each.
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+    struct At(i32);
+
+    struct Walk(i32);
+
+    struct Line {
+        goal: i32,
+        guided: bool,
+    }
+
+    impl Transform<At, u32> for Walk {
+        fn cost(&self) -> u32 {
+            1
+        }
+
+        fn transform(&self, at: &At) -> At {
+            At(at.0 + self.0)
+        }
+    }
+
+    impl GameState<Line, u32> for At {
+        type Steps = [Walk; 4];
+
+        fn accept(&self, _cost: u32, line: &mut Line) -> bool {
+            self.0 == line.goal
+        }
+
+        fn steps(&self, _line: &mut Line) -> Self::Steps {
+            [Walk(-3), Walk(-1), Walk(1), Walk(3)]
+        }
+
+        fn heuristic(&self, line: &mut Line) -> u32 {
+            if line.guided {
+                (line.goal - self.0).unsigned_abs().div_ceil(3)
+            } else {
+                0
+            }
+        }
+    }
+
+    #[test]
+    fn path_adds_up() {
+        let path = At(0)
+            .shortest_path(&mut Line {
+                goal: 7,
+                guided: false,
+            })
+            .unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.states.first(), Some(&At(0)));
+        assert_eq!(path.states.last(), Some(&At(7)));
+        assert_eq!(path.moves.len(), 3);
+        assert_eq!(path.moves.iter().map(|w| w.0).sum::<i32>(), 7);
+    }
+
+    #[test]
+    fn heuristic_visits_less() {
+        let run = |guided| search([At(0)], &mut Line { goal: 20, guided });
+        let (plain, guided) = (run(false), run(true));
+        assert_eq!(plain.path.unwrap().cost, 8);
+        assert_eq!(guided.path.unwrap().cost, 8);
+        assert!(guided.visited < plain.visited);
+    }
+
+    #[test]
+    fn closest_start_wins() {
+        let mut line = Line {
+            goal: 10,
+            guided: true,
+        };
+        let path = search([At(0), At(9), At(-5)], &mut line).path.unwrap();
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.states, [At(9), At(10)]);
+    }
+}
@@ -0,0 +1,13 @@
+pub use arrayvec::ArrayVec;
+pub use hashbrown::hash_map::Entry;
+pub use hashbrown::{HashMap, HashSet};
+pub use itertools::Itertools;
+pub use lazy_static::lazy_static;
+pub use num_bigint::BigInt;
+pub use num_integer;
+pub use num_traits::PrimInt;
+pub use pest::iterators::{Pair, Pairs};
+pub use pest::Parser;
+pub use pest_derive::Parser;
+pub use std::cmp::{max, min};
+pub use std::collections::BTreeMap;
@@ -0,0 +1,49 @@
+use aoc_prelude::*;
+
+#[derive(Parser)]
+#[grammar_inline = r#"
+num = { ASCII_DIGIT+ }
+list = { SOI ~ num ~ ("," ~ num)* ~ EOI }
+"#]
+struct ListParser;
+
+#[test]
+fn pest_parser() {
+    let list = ListParser::parse(Rule::list, "4,8,15")
+        .unwrap()
+        .next()
+        .unwrap();
+    let nums = list
+        .into_inner()
+        .filter(|p| p.as_rule() == Rule::num)
+        .map(|p: Pair<Rule>| p.as_str().parse::<u32>().unwrap())
+        .collect_vec();
+    assert_eq!(nums, [4, 8, 15]);
+    assert!(ListParser::parse(Rule::list, "4,,8").is_err());
+}
+
+#[test]
+fn collections() {
+    let mut counts = HashMap::new();
+    for c in "abracadabra".chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&'a'], 5);
+    if let Entry::Occupied(e) = counts.entry('b') {
+        assert_eq!(e.remove(), 2);
+    }
+    assert_eq!(counts.keys().copied().collect::<HashSet<_>>().len(), 4);
+
+    let mut stack = ArrayVec::<u8, 2>::new();
+    stack.push(1);
+    assert_eq!(stack.try_push(2), Ok(()));
+    assert!(stack.try_push(3).is_err());
+}
+
+#[test]
+fn numbers() {
+    let big = (1..=30).map(BigInt::from).product::<BigInt>();
+    assert_eq!(big.to_string(), "265252859812191058636308480000000");
+    assert_eq!(num_integer::lcm(4u64, 6), 12);
+    assert_eq!(max(3, min(7, 5)), 5);
+}